@@ -1,15 +1,30 @@
 use std::env;
+use std::time::Duration;
 use crate::Snowflake;
+use crate::domain::store::StorageConfig;
 
 pub struct BotConfig {
     pub token: String,
     pub socket_addr: (String, u16),
+    #[allow(dead_code)]
     pub intent_bits: u64,
     pub app_id: Snowflake,
     pub bot_url: String,
     pub base_url: String,
     pub public_key: ed25519_dalek::PublicKey,
-    pub storage_path: String,
+    pub storage: StorageConfig,
+    /// Time after which slow handlers are deferred, `None` disables automatic deferral
+    pub deferral_budget: Option<Duration>,
+    /// Time between sweeps of expired notes, `None` disables the sweeper
+    pub sweep_interval: Option<Duration>,
+    /// Whether commands are registered with Discord on startup
+    pub declare_commands: bool,
+    /// Whether command cooldowns survive restarts
+    pub persist_cooldowns: bool,
+    /// Channel which receives reports of unexpected errors
+    pub admin_channel_id: Option<Snowflake>,
+    /// Users who may manage global notes
+    pub admin_ids: Vec<Snowflake>,
 }
 
 #[derive(thiserror::Error, Debug)]
@@ -37,7 +52,14 @@ impl BotConfig {
         const CLIENT_ID: &str = "CLID";
         const BOT_URL: &str = "URL";
         const PUBLIC_KEY: &str = "PUBLIC_KEY";
+        const STORAGE_BACKEND: &str = "STORAGE_BACKEND";
         const STORAGE_PATH: &str = "STORAGE_PATH";
+        const DEFERRAL_BUDGET: &str = "DEFERRAL_BUDGET_MS";
+        const SWEEP_INTERVAL: &str = "SWEEP_INTERVAL_SECS";
+        const DECLARE_COMMANDS: &str = "DECLARE_COMMANDS";
+        const PERSIST_COOLDOWNS: &str = "PERSIST_COOLDOWNS";
+        const ADMIN_CHANNEL_ID: &str = "ADMIN_CHANNEL_ID";
+        const ADMIN_IDS: &str = "ADMIN_IDS";
 
         let token = env::var(DISCORD_TOKEN)
             .map_err(|_| MissingRequired { field_name: DISCORD_TOKEN })?;
@@ -85,16 +107,81 @@ impl BotConfig {
                     field_name: PUBLIC_KEY,
                     expected: "Valid ed25519 public key obtained from Discord",
                 })?;
-            let key = ed25519_dalek::PublicKey::from_bytes(&byte_vec)
+            ed25519_dalek::PublicKey::from_bytes(&byte_vec)
                 .map_err(|_| InvalidValue {
                     field_name: PUBLIC_KEY,
                     expected: "Valid ed25519 public key obtained from Discord",
-                })?;
-            key
+                })?
+        };
+
+        let storage = {
+            let storage_path = || env::var(STORAGE_PATH)
+                .map_err(|_| MissingRequired { field_name: STORAGE_PATH });
+            match env::var(STORAGE_BACKEND).as_deref().unwrap_or("sled") {
+                "sled" => StorageConfig::Sled { path: storage_path()? },
+                "sqlite" => StorageConfig::Sqlite { path: storage_path()? },
+                "memory" => StorageConfig::Memory,
+                _ => return Err(InvalidValue {
+                    field_name: STORAGE_BACKEND,
+                    expected: "sled, sqlite or memory",
+                }),
+            }
         };
 
-        let storage_path = env::var(STORAGE_PATH)
-            .map_err(|_| MissingRequired { field_name: STORAGE_PATH })?;
+        let deferral_budget = env::var(DEFERRAL_BUDGET)
+            .map(|s| s.parse::<u64>())
+            .unwrap_or(Ok(2500))
+            .map_err(|_| InvalidValue {
+                field_name: DEFERRAL_BUDGET,
+                expected: "Milliseconds in range 0..u64::MAX, 0 disables deferral",
+            })
+            .map(|ms| Some(Duration::from_millis(ms)).filter(|d| !d.is_zero()))?;
+
+        let sweep_interval = env::var(SWEEP_INTERVAL)
+            .map(|s| s.parse::<u64>())
+            .unwrap_or(Ok(60))
+            .map_err(|_| InvalidValue {
+                field_name: SWEEP_INTERVAL,
+                expected: "Seconds in range 0..u64::MAX, 0 disables the sweeper",
+            })
+            .map(|secs| Some(Duration::from_secs(secs)).filter(|d| !d.is_zero()))?;
+
+        let declare_commands = env::var(DECLARE_COMMANDS)
+            .map(|s| s.parse::<bool>())
+            .unwrap_or(Ok(false))
+            .map_err(|_| InvalidValue {
+                field_name: DECLARE_COMMANDS,
+                expected: "true or false",
+            })?;
+
+        let persist_cooldowns = env::var(PERSIST_COOLDOWNS)
+            .map(|s| s.parse::<bool>())
+            .unwrap_or(Ok(false))
+            .map_err(|_| InvalidValue {
+                field_name: PERSIST_COOLDOWNS,
+                expected: "true or false",
+            })?;
+
+        let admin_channel_id = env::var(ADMIN_CHANNEL_ID)
+            .ok()
+            .map(|s| s.parse())
+            .transpose()
+            .map_err(|_| InvalidValue {
+                field_name: ADMIN_CHANNEL_ID,
+                expected: "Valid snowflake id of a channel",
+            })?;
+
+        let admin_ids = env::var(ADMIN_IDS)
+            .unwrap_or_default()
+            .split(',')
+            .map(str::trim)
+            .filter(|id| !id.is_empty())
+            .map(|id| id.parse())
+            .collect::<Result<_, _>>()
+            .map_err(|_| InvalidValue {
+                field_name: ADMIN_IDS,
+                expected: "Comma separated snowflake ids of users",
+            })?;
 
         Ok(BotConfig {
             token,
@@ -104,7 +191,13 @@ impl BotConfig {
             app_id,
             bot_url,
             public_key,
-            storage_path,
+            storage,
+            deferral_budget,
+            sweep_interval,
+            declare_commands,
+            persist_cooldowns,
+            admin_channel_id,
+            admin_ids,
         })
     }
 }
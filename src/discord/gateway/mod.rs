@@ -1 +1,2 @@
+#[allow(dead_code)]
 struct GatewayConnection;
\ No newline at end of file
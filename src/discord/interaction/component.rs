@@ -0,0 +1,480 @@
+use super::modal::TextInput;
+use crate::discord::{ChannelType, Snowflake};
+use serde::de::Error as _;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::Value;
+use serde_repr::{Deserialize_repr, Serialize_repr};
+
+#[derive(Serialize_repr, Deserialize_repr, Debug, Eq, PartialEq, Clone, Copy)]
+#[repr(u8)]
+#[non_exhaustive]
+pub enum ComponentType {
+    ActionRow = 1,
+    Button = 2,
+    StringSelect = 3,
+    TextInput = 4,
+    UserSelect = 5,
+    RoleSelect = 6,
+    MentionableSelect = 7,
+    ChannelSelect = 8,
+}
+
+#[derive(Debug, Clone)]
+pub enum MessageComponent {
+    ActionRow(ActionRow),
+    Button(Button),
+    StringSelect(SelectMenu),
+    UserSelect(SelectMenu),
+    RoleSelect(SelectMenu),
+    MentionableSelect(SelectMenu),
+    ChannelSelect(SelectMenu),
+    TextInput(TextInput),
+}
+
+impl MessageComponent {
+    pub fn component_type(&self) -> ComponentType {
+        match self {
+            MessageComponent::ActionRow(_) => ComponentType::ActionRow,
+            MessageComponent::Button(_) => ComponentType::Button,
+            MessageComponent::StringSelect(_) => ComponentType::StringSelect,
+            MessageComponent::UserSelect(_) => ComponentType::UserSelect,
+            MessageComponent::RoleSelect(_) => ComponentType::RoleSelect,
+            MessageComponent::MentionableSelect(_) => ComponentType::MentionableSelect,
+            MessageComponent::ChannelSelect(_) => ComponentType::ChannelSelect,
+            MessageComponent::TextInput(_) => ComponentType::TextInput,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct TaggedComponent<'a, T> {
+    #[serde(rename = "type")]
+    component_type: ComponentType,
+    #[serde(flatten)]
+    component: &'a T,
+}
+
+impl Serialize for MessageComponent {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let component_type = self.component_type();
+        match self {
+            MessageComponent::ActionRow(component) => TaggedComponent {
+                component_type,
+                component,
+            }
+            .serialize(serializer),
+            MessageComponent::Button(component) => TaggedComponent {
+                component_type,
+                component,
+            }
+            .serialize(serializer),
+            MessageComponent::StringSelect(component)
+            | MessageComponent::UserSelect(component)
+            | MessageComponent::RoleSelect(component)
+            | MessageComponent::MentionableSelect(component)
+            | MessageComponent::ChannelSelect(component) => TaggedComponent {
+                component_type,
+                component,
+            }
+            .serialize(serializer),
+            MessageComponent::TextInput(component) => TaggedComponent {
+                component_type,
+                component,
+            }
+            .serialize(serializer),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for MessageComponent {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let value = Value::deserialize(deserializer)?;
+        let component_type = value
+            .get("type")
+            .cloned()
+            .ok_or_else(|| D::Error::missing_field("type"))
+            .and_then(|t| ComponentType::deserialize(t).map_err(D::Error::custom))?;
+        let component = match component_type {
+            ComponentType::ActionRow => {
+                serde_json::from_value(value).map(MessageComponent::ActionRow)
+            }
+            ComponentType::Button => serde_json::from_value(value).map(MessageComponent::Button),
+            ComponentType::StringSelect => {
+                serde_json::from_value(value).map(MessageComponent::StringSelect)
+            }
+            ComponentType::UserSelect => {
+                serde_json::from_value(value).map(MessageComponent::UserSelect)
+            }
+            ComponentType::RoleSelect => {
+                serde_json::from_value(value).map(MessageComponent::RoleSelect)
+            }
+            ComponentType::MentionableSelect => {
+                serde_json::from_value(value).map(MessageComponent::MentionableSelect)
+            }
+            ComponentType::ChannelSelect => {
+                serde_json::from_value(value).map(MessageComponent::ChannelSelect)
+            }
+            ComponentType::TextInput => {
+                serde_json::from_value(value).map(MessageComponent::TextInput)
+            }
+        };
+        component.map_err(D::Error::custom)
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct ActionRow {
+    pub components: Vec<MessageComponent>,
+}
+
+impl ActionRow {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_component(mut self, component: impl Into<MessageComponent>) -> Self {
+        self.components.push(component.into());
+        self
+    }
+}
+
+impl From<ActionRow> for MessageComponent {
+    fn from(row: ActionRow) -> Self {
+        MessageComponent::ActionRow(row)
+    }
+}
+
+#[derive(Serialize_repr, Deserialize_repr, Debug, Eq, PartialEq, Clone, Copy)]
+#[repr(u8)]
+pub enum ButtonStyle {
+    Primary = 1,
+    Secondary = 2,
+    Success = 3,
+    Danger = 4,
+    Link = 5,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Button {
+    pub style: ButtonStyle,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub emoji: Option<PartialEmoji>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub custom_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    #[serde(default)]
+    pub disabled: bool,
+}
+
+impl Button {
+    fn with_custom_id(style: ButtonStyle, custom_id: &str, label: &str) -> Self {
+        Button {
+            style,
+            label: Some(label.to_owned()),
+            emoji: None,
+            custom_id: Some(custom_id.to_owned()),
+            url: None,
+            disabled: false,
+        }
+    }
+
+    #[allow(dead_code)]
+    pub fn primary(custom_id: &str, label: &str) -> Self {
+        Self::with_custom_id(ButtonStyle::Primary, custom_id, label)
+    }
+
+    pub fn secondary(custom_id: &str, label: &str) -> Self {
+        Self::with_custom_id(ButtonStyle::Secondary, custom_id, label)
+    }
+
+    #[allow(dead_code)]
+    pub fn success(custom_id: &str, label: &str) -> Self {
+        Self::with_custom_id(ButtonStyle::Success, custom_id, label)
+    }
+
+    pub fn danger(custom_id: &str, label: &str) -> Self {
+        Self::with_custom_id(ButtonStyle::Danger, custom_id, label)
+    }
+
+    /// Link buttons open `url` in the browser and never produce an interaction
+    #[allow(dead_code)]
+    pub fn link(url: &str, label: &str) -> Self {
+        Button {
+            style: ButtonStyle::Link,
+            label: Some(label.to_owned()),
+            emoji: None,
+            custom_id: None,
+            url: Some(url.to_owned()),
+            disabled: false,
+        }
+    }
+
+    #[allow(dead_code)]
+    pub fn with_emoji(mut self, emoji: PartialEmoji) -> Self {
+        self.emoji = Some(emoji);
+        self
+    }
+
+    pub fn disabled(mut self) -> Self {
+        self.disabled = true;
+        self
+    }
+}
+
+impl From<Button> for MessageComponent {
+    fn from(button: Button) -> Self {
+        MessageComponent::Button(button)
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct PartialEmoji {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<Snowflake>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub animated: bool,
+}
+
+#[allow(dead_code)]
+impl PartialEmoji {
+    pub fn unicode(emoji: &str) -> Self {
+        PartialEmoji {
+            id: None,
+            name: Some(emoji.to_owned()),
+            animated: false,
+        }
+    }
+
+    pub fn custom(id: Snowflake, name: &str) -> Self {
+        PartialEmoji {
+            id: Some(id),
+            name: Some(name.to_owned()),
+            animated: false,
+        }
+    }
+}
+
+/// Select menu of any kind. `options` are used only by string selects
+/// and `channel_types` only by channel selects.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SelectMenu {
+    pub custom_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub options: Option<Vec<SelectOption>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub channel_types: Option<Vec<ChannelType>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub placeholder: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_values: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_values: Option<u8>,
+    #[serde(default)]
+    pub disabled: bool,
+}
+
+#[allow(dead_code)]
+impl SelectMenu {
+    fn new(custom_id: &str) -> Self {
+        SelectMenu {
+            custom_id: custom_id.to_owned(),
+            options: None,
+            channel_types: None,
+            placeholder: None,
+            min_values: None,
+            max_values: None,
+            disabled: false,
+        }
+    }
+
+    pub fn string(custom_id: &str, options: Vec<SelectOption>) -> MessageComponent {
+        MessageComponent::StringSelect(SelectMenu {
+            options: Some(options),
+            ..Self::new(custom_id)
+        })
+    }
+
+    pub fn user(custom_id: &str) -> MessageComponent {
+        MessageComponent::UserSelect(Self::new(custom_id))
+    }
+
+    pub fn role(custom_id: &str) -> MessageComponent {
+        MessageComponent::RoleSelect(Self::new(custom_id))
+    }
+
+    pub fn mentionable(custom_id: &str) -> MessageComponent {
+        MessageComponent::MentionableSelect(Self::new(custom_id))
+    }
+
+    pub fn channel(custom_id: &str, channel_types: Vec<ChannelType>) -> MessageComponent {
+        MessageComponent::ChannelSelect(SelectMenu {
+            channel_types: Some(channel_types).filter(|t| !t.is_empty()),
+            ..Self::new(custom_id)
+        })
+    }
+}
+
+#[allow(dead_code)]
+impl MessageComponent {
+    /// Applies `f` to the inner select menu, leaving other components untouched
+    fn map_select(mut self, f: impl FnOnce(&mut SelectMenu)) -> Self {
+        match &mut self {
+            MessageComponent::StringSelect(menu)
+            | MessageComponent::UserSelect(menu)
+            | MessageComponent::RoleSelect(menu)
+            | MessageComponent::MentionableSelect(menu)
+            | MessageComponent::ChannelSelect(menu) => f(menu),
+            MessageComponent::ActionRow(_)
+            | MessageComponent::Button(_)
+            | MessageComponent::TextInput(_) => {}
+        }
+        self
+    }
+
+    pub fn with_placeholder(self, placeholder: &str) -> Self {
+        self.map_select(|menu| menu.placeholder = Some(placeholder.to_owned()))
+    }
+
+    pub fn with_values_range(self, min_values: u8, max_values: u8) -> Self {
+        self.map_select(|menu| {
+            menu.min_values = Some(min_values);
+            menu.max_values = Some(max_values);
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SelectOption {
+    pub label: String,
+    pub value: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub emoji: Option<PartialEmoji>,
+    #[serde(default)]
+    pub default: bool,
+}
+
+#[allow(dead_code)]
+impl SelectOption {
+    pub fn new(label: &str, value: &str) -> Self {
+        SelectOption {
+            label: label.to_owned(),
+            value: value.to_owned(),
+            description: None,
+            emoji: None,
+            default: false,
+        }
+    }
+
+    pub fn with_description(mut self, description: &str) -> Self {
+        self.description = Some(description.to_owned());
+        self
+    }
+
+    pub fn selected(mut self) -> Self {
+        self.default = true;
+        self
+    }
+}
+
+/// Value chosen by the user in a select menu:
+/// option value for string selects and snowflake id for others
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+#[serde(transparent)]
+pub struct SelectOptionValue(pub String);
+
+#[allow(dead_code)]
+impl SelectOptionValue {
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    pub fn as_snowflake(&self) -> Option<Snowflake> {
+        self.0.parse().ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ActionRow, Button, MessageComponent, SelectMenu, SelectOption};
+    use crate::discord::ChannelType;
+    use serde_json::json;
+
+    #[test]
+    fn serialize_action_row_with_buttons() {
+        let row: MessageComponent = ActionRow::new()
+            .with_component(Button::primary("ls:2", "Next"))
+            .with_component(Button::link("https://example.com", "Docs").disabled())
+            .into();
+
+        let json = serde_json::to_value(&row).unwrap();
+        assert_eq!(
+            json,
+            json!({
+                "type": 1,
+                "components": [
+                    {"type": 2, "style": 1, "label": "Next", "custom_id": "ls:2", "disabled": false},
+                    {"type": 2, "style": 5, "label": "Docs", "url": "https://example.com", "disabled": true}
+                ]
+            })
+        );
+    }
+
+    #[test]
+    fn serialize_selects() {
+        let string_select =
+            SelectMenu::string("pick", vec![SelectOption::new("One", "1").selected()])
+                .with_placeholder("Choose")
+                .with_values_range(1, 1);
+        assert_eq!(
+            serde_json::to_value(&string_select).unwrap(),
+            json!({
+                "type": 3,
+                "custom_id": "pick",
+                "options": [{"label": "One", "value": "1", "default": true}],
+                "placeholder": "Choose",
+                "min_values": 1,
+                "max_values": 1,
+                "disabled": false
+            })
+        );
+
+        let channel_select = SelectMenu::channel("where", vec![ChannelType::GuildText]);
+        assert_eq!(
+            serde_json::to_value(&channel_select).unwrap(),
+            json!({"type": 8, "custom_id": "where", "channel_types": [0], "disabled": false})
+        );
+    }
+
+    #[test]
+    fn deserialize_component_by_type() {
+        let json = json!({
+            "type": 1,
+            "components": [{"type": 6, "custom_id": "roles"}, {"type": 2, "style": 4, "custom_id": "del"}]
+        });
+
+        let row: MessageComponent = serde_json::from_value(json).unwrap();
+        let components = match row {
+            MessageComponent::ActionRow(row) => row.components,
+            other => panic!("Expected action row, got {:?}", other),
+        };
+        assert!(
+            matches!(&components[0], MessageComponent::RoleSelect(menu) if menu.custom_id == "roles")
+        );
+        assert!(
+            matches!(&components[1], MessageComponent::Button(button) if button.custom_id.as_deref() == Some("del"))
+        );
+    }
+}
@@ -0,0 +1,314 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+pub const TITLE_LIMIT: usize = 256;
+pub const DESCRIPTION_LIMIT: usize = 4096;
+pub const FIELDS_LIMIT: usize = 25;
+pub const FIELD_NAME_LIMIT: usize = 256;
+pub const FIELD_VALUE_LIMIT: usize = 1024;
+pub const FOOTER_TEXT_LIMIT: usize = 2048;
+pub const AUTHOR_NAME_LIMIT: usize = 256;
+/// Limit for the sum of all texts in embed
+pub const TOTAL_LIMIT: usize = 6000;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct Embed {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timestamp: Option<DateTime<Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub color: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub footer: Option<EmbedFooter>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub image: Option<EmbedMedia>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub thumbnail: Option<EmbedMedia>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub author: Option<EmbedAuthor>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fields: Vec<EmbedField>,
+}
+
+impl Embed {
+    pub fn build() -> EmbedBuilder {
+        EmbedBuilder::default()
+    }
+
+    /// Length of all texts which count towards `TOTAL_LIMIT`
+    pub fn text_length(&self) -> usize {
+        fn len(text: &Option<String>) -> usize {
+            text.as_deref().map(char_count).unwrap_or(0)
+        }
+        len(&self.title)
+            + len(&self.description)
+            + self.footer.as_ref().map(|f| char_count(&f.text)).unwrap_or(0)
+            + self.author.as_ref().map(|a| char_count(&a.name)).unwrap_or(0)
+            + self
+                .fields
+                .iter()
+                .map(|f| char_count(&f.name) + char_count(&f.value))
+                .sum::<usize>()
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct EmbedFooter {
+    pub text: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub icon_url: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct EmbedMedia {
+    pub url: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub height: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub width: Option<u32>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct EmbedAuthor {
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub icon_url: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct EmbedField {
+    pub name: String,
+    pub value: String,
+    #[serde(default)]
+    pub inline: bool,
+}
+
+#[derive(thiserror::Error, Debug, Eq, PartialEq)]
+pub enum EmbedError {
+    #[error("Embed {} is {} characters long, limit is {}", part, length, limit)]
+    TooLong {
+        part: &'static str,
+        length: usize,
+        limit: usize,
+    },
+    #[error("Embed has {} fields, limit is {}", count, FIELDS_LIMIT)]
+    TooManyFields { count: usize },
+    #[error("Embed is {} characters long in total, limit is {}", length, TOTAL_LIMIT)]
+    TotalTooLong { length: usize },
+}
+
+#[derive(Default)]
+pub struct EmbedBuilder {
+    embed: Embed,
+}
+
+impl EmbedBuilder {
+    pub fn with_title(mut self, title: &str) -> Self {
+        self.embed.title = Some(title.to_owned());
+        self
+    }
+
+    pub fn with_description(mut self, description: &str) -> Self {
+        self.embed.description = Some(description.to_owned());
+        self
+    }
+
+    #[allow(dead_code)]
+    pub fn with_url(mut self, url: &str) -> Self {
+        self.embed.url = Some(url.to_owned());
+        self
+    }
+
+    #[allow(dead_code)]
+    pub fn with_timestamp(mut self, timestamp: DateTime<Utc>) -> Self {
+        self.embed.timestamp = Some(timestamp);
+        self
+    }
+
+    pub fn with_color(mut self, color: u32) -> Self {
+        self.embed.color = Some(color);
+        self
+    }
+
+    pub fn with_footer(mut self, text: &str, icon_url: Option<&str>) -> Self {
+        self.embed.footer = Some(EmbedFooter {
+            text: text.to_owned(),
+            icon_url: icon_url.map(String::from),
+        });
+        self
+    }
+
+    #[allow(dead_code)]
+    pub fn with_image(mut self, url: &str) -> Self {
+        self.embed.image = Some(EmbedMedia {
+            url: url.to_owned(),
+            height: None,
+            width: None,
+        });
+        self
+    }
+
+    #[allow(dead_code)]
+    pub fn with_thumbnail(mut self, url: &str) -> Self {
+        self.embed.thumbnail = Some(EmbedMedia {
+            url: url.to_owned(),
+            height: None,
+            width: None,
+        });
+        self
+    }
+
+    #[allow(dead_code)]
+    pub fn with_author(mut self, name: &str, url: Option<&str>, icon_url: Option<&str>) -> Self {
+        self.embed.author = Some(EmbedAuthor {
+            name: name.to_owned(),
+            url: url.map(String::from),
+            icon_url: icon_url.map(String::from),
+        });
+        self
+    }
+
+    #[allow(dead_code)]
+    pub fn with_field(mut self, name: &str, value: &str) -> Self {
+        self.embed.fields.push(EmbedField {
+            name: name.to_owned(),
+            value: value.to_owned(),
+            inline: false,
+        });
+        self
+    }
+
+    pub fn with_inline_field(mut self, name: &str, value: &str) -> Self {
+        self.embed.fields.push(EmbedField {
+            name: name.to_owned(),
+            value: value.to_owned(),
+            inline: true,
+        });
+        self
+    }
+
+    /// Validates the embed against Discord limits
+    pub fn finish(self) -> Result<Embed, EmbedError> {
+        let embed = self.embed;
+        check("title", embed.title.as_deref(), TITLE_LIMIT)?;
+        check("description", embed.description.as_deref(), DESCRIPTION_LIMIT)?;
+        check(
+            "footer text",
+            embed.footer.as_ref().map(|f| f.text.as_str()),
+            FOOTER_TEXT_LIMIT,
+        )?;
+        check(
+            "author name",
+            embed.author.as_ref().map(|a| a.name.as_str()),
+            AUTHOR_NAME_LIMIT,
+        )?;
+        if embed.fields.len() > FIELDS_LIMIT {
+            return Err(EmbedError::TooManyFields {
+                count: embed.fields.len(),
+            });
+        }
+        for field in &embed.fields {
+            check("field name", Some(&field.name), FIELD_NAME_LIMIT)?;
+            check("field value", Some(&field.value), FIELD_VALUE_LIMIT)?;
+        }
+        let length = embed.text_length();
+        if length > TOTAL_LIMIT {
+            return Err(EmbedError::TotalTooLong { length });
+        }
+        Ok(embed)
+    }
+}
+
+fn check(part: &'static str, text: Option<&str>, limit: usize) -> Result<(), EmbedError> {
+    match text.map(char_count) {
+        Some(length) if length > limit => Err(EmbedError::TooLong {
+            part,
+            length,
+            limit,
+        }),
+        _ => Ok(()),
+    }
+}
+
+fn char_count(text: &str) -> usize {
+    text.chars().count()
+}
+
+/// Cuts text to at most `limit` characters, marking the cut with an ellipsis
+pub fn truncate(text: &str, limit: usize) -> String {
+    if char_count(text) <= limit {
+        text.to_owned()
+    } else {
+        let mut truncated: String = text.chars().take(limit.saturating_sub(1)).collect();
+        truncated.push('…');
+        truncated
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{truncate, Embed, EmbedError, FIELD_VALUE_LIMIT};
+    use chrono::{TimeZone, Utc};
+    use serde_json::json;
+
+    #[test]
+    fn serialize_embed() {
+        let embed = Embed::build()
+            .with_title("key")
+            .with_description("value")
+            .with_color(0x5865F2)
+            .with_timestamp(Utc.ymd(2022, 6, 1).and_hms(12, 0, 0))
+            .with_footer("footer", None)
+            .with_inline_field("name", "value")
+            .finish()
+            .unwrap();
+
+        assert_eq!(
+            serde_json::to_value(&embed).unwrap(),
+            json!({
+                "title": "key",
+                "description": "value",
+                "timestamp": "2022-06-01T12:00:00Z",
+                "color": 0x5865F2,
+                "footer": {"text": "footer"},
+                "fields": [{"name": "name", "value": "value", "inline": true}]
+            })
+        );
+    }
+
+    #[test]
+    fn reject_too_many_fields() {
+        let builder = (0..26).fold(Embed::build(), |b, i| b.with_field(&i.to_string(), "v"));
+        assert_eq!(
+            builder.finish().unwrap_err(),
+            EmbedError::TooManyFields { count: 26 }
+        );
+    }
+
+    #[test]
+    fn reject_long_field_value() {
+        let value = "x".repeat(FIELD_VALUE_LIMIT + 1);
+        let result = Embed::build().with_field("name", &value).finish();
+        assert!(matches!(result, Err(EmbedError::TooLong { part: "field value", .. })));
+    }
+
+    #[test]
+    fn reject_total_length() {
+        let value = "x".repeat(1000);
+        let builder = (0..7).fold(Embed::build(), |b, i| b.with_field(&i.to_string(), &value));
+        assert!(matches!(builder.finish(), Err(EmbedError::TotalTooLong { .. })));
+    }
+
+    #[test]
+    fn truncate_text() {
+        assert_eq!(truncate("short", 10), "short");
+        assert_eq!(truncate("привет мир", 7), "привет…");
+    }
+}
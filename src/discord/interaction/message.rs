@@ -0,0 +1,262 @@
+use std::ops::{BitOr, BitOrAssign};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::component::MessageComponent;
+use super::embed::Embed;
+use super::user::{GuildMember, User};
+use crate::discord::Snowflake;
+
+#[derive(Deserialize, Debug, Clone)]
+#[allow(dead_code)]
+pub struct Message {
+    pub id: Snowflake,
+    pub channel_id: Snowflake,
+    pub guild_id: Option<Snowflake>,
+    pub author: User,
+    /// Partial member of the author, without `user`. Only for messages in guilds.
+    pub member: Option<GuildMember>,
+    #[serde(default)]
+    pub content: String,
+    pub timestamp: DateTime<Utc>,
+    pub edited_timestamp: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub tts: bool,
+    #[serde(default)]
+    pub mention_everyone: bool,
+    #[serde(default)]
+    pub mentions: Vec<User>,
+    #[serde(default)]
+    pub mention_roles: Vec<Snowflake>,
+    #[serde(default)]
+    pub attachments: Vec<Attachment>,
+    #[serde(default)]
+    pub embeds: Vec<Embed>,
+    #[serde(default)]
+    pub pinned: bool,
+    pub webhook_id: Option<Snowflake>,
+    #[serde(rename = "type", default)]
+    pub message_type: u8,
+    pub application_id: Option<Snowflake>,
+    #[serde(default)]
+    pub flags: MessageFlags,
+    #[serde(default)]
+    pub components: Vec<MessageComponent>,
+}
+
+impl Message {
+    /// Link which opens the message in the client
+    #[allow(dead_code)]
+    pub fn jump_url(&self) -> String {
+        match self.guild_id {
+            Some(guild_id) => format!(
+                "https://discord.com/channels/{}/{}/{}",
+                guild_id, self.channel_id, self.id
+            ),
+            None => format!("https://discord.com/channels/@me/{}/{}", self.channel_id, self.id),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Attachment {
+    pub id: Snowflake,
+    pub filename: String,
+    pub description: Option<String>,
+    pub content_type: Option<String>,
+    pub size: u64,
+    pub url: String,
+    pub proxy_url: String,
+    pub height: Option<u32>,
+    pub width: Option<u32>,
+    #[serde(default)]
+    pub ephemeral: bool,
+}
+
+/// File uploaded along with a message
+#[derive(Debug, Clone)]
+pub struct FileUpload {
+    pub filename: String,
+    pub content_type: String,
+    pub data: Vec<u8>,
+}
+
+impl FileUpload {
+    pub fn new(filename: &str, content_type: &str, data: Vec<u8>) -> Self {
+        FileUpload {
+            filename: filename.to_owned(),
+            content_type: content_type.to_owned(),
+            data,
+        }
+    }
+}
+
+/// Entry of `attachments` of a sent message. `id` of a new attachment is the index of its file.
+#[derive(Serialize, Debug, Clone)]
+pub struct AttachmentReference {
+    pub id: u64,
+    pub filename: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Eq, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum AllowedMentionType {
+    Roles,
+    Users,
+    Everyone,
+}
+
+/// Controls which mentions in the message content actually ping.
+/// Default value allows no mentions at all.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct AllowedMention {
+    pub parse: Vec<AllowedMentionType>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub roles: Vec<Snowflake>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub users: Vec<Snowflake>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub replied_user: bool,
+}
+
+#[allow(dead_code)]
+impl AllowedMention {
+    pub fn none() -> Self {
+        Self::default()
+    }
+
+    /// Allows every mention of given type. Discord rejects messages which
+    /// parse `Users` or `Roles` and also list exact ids of the same type.
+    pub fn with_parse(mut self, mention_type: AllowedMentionType) -> Self {
+        if !self.parse.contains(&mention_type) {
+            self.parse.push(mention_type);
+        }
+        self
+    }
+
+    pub fn with_user(mut self, user_id: Snowflake) -> Self {
+        self.users.push(user_id);
+        self
+    }
+
+    pub fn with_role(mut self, role_id: Snowflake) -> Self {
+        self.roles.push(role_id);
+        self
+    }
+
+    pub fn with_replied_user(mut self) -> Self {
+        self.replied_user = true;
+        self
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, Eq, PartialEq)]
+#[serde(transparent)]
+pub struct MessageFlags(u64);
+
+#[allow(dead_code)]
+impl MessageFlags {
+    pub const CROSSPOSTED: MessageFlags = MessageFlags(1 << 0);
+    pub const IS_CROSSPOST: MessageFlags = MessageFlags(1 << 1);
+    pub const SUPPRESS_EMBEDS: MessageFlags = MessageFlags(1 << 2);
+    pub const SOURCE_MESSAGE_DELETED: MessageFlags = MessageFlags(1 << 3);
+    pub const URGENT: MessageFlags = MessageFlags(1 << 4);
+    pub const HAS_THREAD: MessageFlags = MessageFlags(1 << 5);
+    /// Only the user who invoked the interaction can see the message
+    pub const EPHEMERAL: MessageFlags = MessageFlags(1 << 6);
+    pub const LOADING: MessageFlags = MessageFlags(1 << 7);
+    pub const FAILED_TO_MENTION_SOME_ROLES_IN_THREAD: MessageFlags = MessageFlags(1 << 8);
+    pub const SUPPRESS_NOTIFICATIONS: MessageFlags = MessageFlags(1 << 12);
+
+    pub fn empty() -> Self {
+        MessageFlags(0)
+    }
+
+    pub fn bits(&self) -> u64 {
+        self.0
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    pub fn contains(&self, other: MessageFlags) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    pub fn insert(&mut self, other: MessageFlags) {
+        self.0 |= other.0
+    }
+}
+
+impl BitOr for MessageFlags {
+    type Output = MessageFlags;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        MessageFlags(self.0 | rhs.0)
+    }
+}
+
+impl BitOrAssign for MessageFlags {
+    fn bitor_assign(&mut self, rhs: Self) {
+        self.insert(rhs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AllowedMention, AllowedMentionType, MessageFlags};
+    use crate::discord::interaction::InteractionCallbackMessage;
+    use crate::discord::Snowflake;
+    use serde_json::json;
+
+    #[test]
+    fn allowed_mention_defaults_to_nothing() {
+        assert_eq!(
+            serde_json::to_value(AllowedMention::default()).unwrap(),
+            json!({"parse": []})
+        );
+    }
+
+    #[test]
+    fn serialize_allowed_mention() {
+        let mention = AllowedMention::none()
+            .with_parse(AllowedMentionType::Roles)
+            .with_user("42".parse::<Snowflake>().unwrap())
+            .with_replied_user();
+        assert_eq!(
+            serde_json::to_value(mention).unwrap(),
+            json!({"parse": ["roles"], "users": ["42"], "replied_user": true})
+        );
+    }
+
+    #[test]
+    fn callback_message_is_safe_by_default() {
+        let message = InteractionCallbackMessage {
+            content: Some(String::from("@everyone")),
+            ..Default::default()
+        };
+        assert_eq!(
+            serde_json::to_value(message).unwrap(),
+            json!({"content": "@everyone", "allowed_mentions": {"parse": []}})
+        );
+    }
+
+    #[test]
+    fn ephemeral_callback_message() {
+        let message = InteractionCallbackMessage::default().ephemeral();
+        assert_eq!(serde_json::to_value(message).unwrap()["flags"], json!(64));
+    }
+
+    #[test]
+    fn combine_flags() {
+        let flags = MessageFlags::EPHEMERAL | MessageFlags::SUPPRESS_EMBEDS;
+        assert_eq!(flags.bits(), 68);
+        assert!(flags.contains(MessageFlags::EPHEMERAL));
+        assert!(!flags.contains(MessageFlags::URGENT));
+        assert_eq!(serde_json::to_value(flags).unwrap(), json!(68));
+    }
+}
@@ -1,11 +1,26 @@
 use crate::discord::rest::application_command::{
+    ApplicationCommandOptionType,
     ApplicationCommandOptionValue,
     ApplicationCommandType,
 };
 use crate::Snowflake;
 use serde::{Deserialize, Serialize};
 use serde_repr::{Deserialize_repr, Serialize_repr};
-use crate::discord::Locale;
+use crate::discord::{Locale, Permissions};
+
+pub mod component;
+pub mod embed;
+pub mod message;
+pub mod modal;
+pub mod resolved;
+pub mod user;
+
+use component::{ComponentType, MessageComponent, SelectOptionValue};
+use embed::Embed;
+use message::{AllowedMention, Attachment, AttachmentReference, FileUpload, Message, MessageFlags};
+use resolved::ResolvedData;
+use user::{GuildMember, User};
+use modal::Modal;
 
 #[derive(Serialize, Debug)]
 pub struct InteractionCallback {
@@ -19,24 +34,58 @@ pub struct InteractionCallback {
 #[serde(untagged)]
 pub enum InteractionCallbackData {
     Message(InteractionCallbackMessage),
+    Modal(Modal),
 }
 
-#[derive(Serialize, Debug)]
+#[derive(Serialize, Debug, Default)]
 pub struct InteractionCallbackMessage {
     // pub tts: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub content: Option<String>,
-    // pub embeds: Option<Box<[Embed]>>,
-    // pub allowed_mentions: Option<AllowedMention>,
-    // pub flags: Option<u64>,
-    // pub components: Option<Box<[MessageComponent]>>,
-    // pub attachments: Option<Box<[Attachment]>>
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub embeds: Option<Box<[Embed]>>,
+    /// Nothing is pinged unless handler explicitly allows it
+    pub allowed_mentions: AllowedMention,
+    #[serde(skip_serializing_if = "MessageFlags::is_empty")]
+    pub flags: MessageFlags,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub components: Option<Box<[MessageComponent]>>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub attachments: Vec<AttachmentReference>,
+    /// Sent as parts of a multipart request, next to the JSON payload
+    #[serde(skip)]
+    pub files: Vec<FileUpload>,
 }
 
-#[derive(Serialize)]
-pub struct Embed {}
+impl InteractionCallbackMessage {
+    /// Makes message visible only to the user who invoked the interaction
+    pub fn ephemeral(mut self) -> Self {
+        self.flags.insert(MessageFlags::EPHEMERAL);
+        self
+    }
 
-#[derive(Serialize)]
-pub struct AllowedMention {}
+    #[allow(dead_code)]
+    pub fn with_flags(mut self, flags: MessageFlags) -> Self {
+        self.flags.insert(flags);
+        self
+    }
+
+    #[allow(dead_code)]
+    pub fn with_allowed_mentions(mut self, allowed_mentions: AllowedMention) -> Self {
+        self.allowed_mentions = allowed_mentions;
+        self
+    }
+
+    pub fn with_file(mut self, file: FileUpload) -> Self {
+        self.attachments.push(AttachmentReference {
+            id: self.files.len() as u64,
+            filename: file.filename.clone(),
+            description: None,
+        });
+        self.files.push(file);
+        self
+    }
+}
 
 impl InteractionCallback {
     pub fn pong() -> Self {
@@ -52,6 +101,58 @@ impl InteractionCallback {
             data: Some(InteractionCallbackData::Message(message)),
         }
     }
+
+    /// Files to upload with the callback message
+    pub fn files(&self) -> &[FileUpload] {
+        match &self.data {
+            Some(InteractionCallbackData::Message(message)) => &message.files,
+            _ => &[],
+        }
+    }
+
+    /// Message of the callback, if it has one
+    pub fn into_message(self) -> Option<InteractionCallbackMessage> {
+        match self.data {
+            Some(InteractionCallbackData::Message(message)) => Some(message),
+            _ => None,
+        }
+    }
+
+    /// Acknowledges the interaction, showing "thinking" state until the original response is edited
+    pub fn deferred_channel_message_with_source(ephemeral: bool) -> Self {
+        let message = InteractionCallbackMessage::default();
+        InteractionCallback {
+            interaction_response_type: InteractionResponseType::DeferredChannelMessageWithSource,
+            data: Some(InteractionCallbackData::Message(if ephemeral {
+                message.ephemeral()
+            } else {
+                message
+            })),
+        }
+    }
+
+    /// Acknowledges a component interaction, the message is edited later
+    pub fn deferred_update_message() -> Self {
+        InteractionCallback {
+            interaction_response_type: InteractionResponseType::DeferredUpdateMessage,
+            data: None,
+        }
+    }
+
+    /// Response to a component interaction which edits the message the component is attached to
+    pub fn update_message(message: InteractionCallbackMessage) -> Self {
+        InteractionCallback {
+            interaction_response_type: InteractionResponseType::UpdateMessage,
+            data: Some(InteractionCallbackData::Message(message)),
+        }
+    }
+
+    pub fn modal(modal: Modal) -> Self {
+        InteractionCallback {
+            interaction_response_type: InteractionResponseType::Modal,
+            data: Some(InteractionCallbackData::Modal(modal)),
+        }
+    }
 }
 
 #[derive(Serialize_repr, Debug)]
@@ -62,12 +163,14 @@ pub enum InteractionResponseType {
     DeferredChannelMessageWithSource = 5,
     DeferredUpdateMessage = 6,
     UpdateMessage = 7,
+    #[allow(dead_code)]
     ApplicationCommandAutocompleteResult = 8,
     Modal = 9,
 }
 
 #[derive(Deserialize, Debug)]
 pub struct Interaction {
+    #[allow(dead_code)]
     pub application_id: Snowflake,
     pub id: Snowflake,
     pub token: String,
@@ -81,12 +184,26 @@ pub struct Interaction {
     pub guild_id: Option<Snowflake>,
     pub channel_id: Option<Snowflake>,
     pub member: Option<GuildMember>,
+    #[allow(dead_code)]
     pub message: Option<Message>,
+    /// Permissions of the bot in the channel of the interaction
+    pub app_permissions: Option<Permissions>,
     pub locale: Option<Locale>,
     pub guild_locale: Option<Locale>,
+    #[allow(dead_code)]
     pub version: u8,
 }
 
+impl Interaction {
+    /// User who invoked the interaction: member's user in guilds, `user` in DMs
+    pub fn invoking_user(&self) -> Option<&User> {
+        self.member
+            .as_ref()
+            .and_then(|member| member.user.as_ref())
+            .or(self.user.as_ref())
+    }
+}
+
 #[derive(Deserialize_repr, Debug, Eq, PartialEq)]
 #[repr(u8)]
 #[non_exhaustive]
@@ -98,44 +215,69 @@ pub enum InteractionType {
     ModalSubmit = 5,
 }
 
+/// Data of application command, message component and modal submit interactions.
+/// Command fields are empty for components and the other way around.
 #[derive(Deserialize, Debug)]
 pub struct InteractionData {
+    #[serde(default)]
+    #[allow(dead_code)]
     pub id: Snowflake,
+    #[serde(default)]
     pub name: String,
 
     #[serde(rename = "type")]
-    pub command_type: ApplicationCommandType,
+    pub command_type: Option<ApplicationCommandType>,
     pub resolved: Option<ResolvedData>,
     pub options: Option<Box<[ApplicationCommandInteractionDataOption]>>,
+    #[allow(dead_code)]
     pub guild_id: Option<Snowflake>,
     pub custom_id: Option<String>,
+    #[allow(dead_code)]
+    pub component_type: Option<ComponentType>,
+    #[allow(dead_code)]
     pub values: Option<Box<[SelectOptionValue]>>,
     pub target_id: Option<Snowflake>,
     pub components: Option<Box<[MessageComponent]>>,
 }
 
-#[derive(Deserialize, Debug)]
-pub struct MessageComponent {}
+impl InteractionData {
+    /// Value of a top-level command option
+    pub fn option(&self, name: &str) -> Option<&ApplicationCommandOptionValue> {
+        self.options
+            .as_deref()?
+            .iter()
+            .find(|option| option.name == name)
+            .map(|option| &option.value)
+    }
 
-#[derive(Deserialize, Debug)]
-pub struct SelectOptionValue {}
+    /// Attachment uploaded for an attachment option
+    pub fn attachment(&self, name: &str) -> Option<&Attachment> {
+        let id = match self.option(name)? {
+            ApplicationCommandOptionValue::Str(id) => id.parse::<Snowflake>().ok()?,
+            _ => return None,
+        };
+        self.resolved.as_ref()?.attachments.get(&id)
+    }
+
+    /// Message a message command was invoked on
+    pub fn target_message(&self) -> Option<&Message> {
+        let target_id = self.target_id?;
+        self.resolved.as_ref()?.messages.get(&target_id)
+    }
+
+    /// User a user command was invoked on, with their member data in guilds
+    pub fn target_user(&self) -> Option<(&User, Option<&GuildMember>)> {
+        let target_id = self.target_id?;
+        let resolved = self.resolved.as_ref()?;
+        let user = resolved.users.get(&target_id)?;
+        Some((user, resolved.members.get(&target_id)))
+    }
+}
 
 #[derive(Deserialize, Debug, Clone)]
 pub struct ApplicationCommandInteractionDataOption {
     pub name: String,
     #[serde(rename = "type")]
-    pub application_command_option_type: ApplicationCommandType,
+    pub application_command_option_type: ApplicationCommandOptionType,
     pub value: ApplicationCommandOptionValue,
 }
-
-#[derive(Deserialize, Debug)]
-pub struct ResolvedData {}
-
-#[derive(Deserialize, Debug)]
-pub struct GuildMember {}
-
-#[derive(Deserialize, Debug)]
-pub struct User {}
-
-#[derive(Deserialize, Debug)]
-pub struct Message {}
@@ -0,0 +1,236 @@
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+use serde_repr::{Deserialize_repr, Serialize_repr};
+
+use super::component::{ActionRow, MessageComponent};
+use super::InteractionData;
+
+const MAX_MODAL_COMPONENTS: usize = 5;
+
+#[derive(Serialize, Debug)]
+pub struct Modal {
+    pub custom_id: String,
+    pub title: String,
+    pub components: Vec<MessageComponent>,
+}
+
+impl Modal {
+    pub fn build(custom_id: &str, title: &str) -> ModalBuilder {
+        ModalBuilder::new(custom_id, title)
+    }
+}
+
+pub struct ModalBuilder {
+    custom_id: String,
+    title: String,
+    text_inputs: Vec<TextInput>,
+}
+
+impl ModalBuilder {
+    pub fn new(custom_id: &str, title: &str) -> Self {
+        ModalBuilder {
+            custom_id: custom_id.to_owned(),
+            title: title.to_owned(),
+            text_inputs: vec![],
+        }
+    }
+
+    pub fn with_text_input(mut self, text_input: TextInput) -> Self {
+        self.text_inputs.push(text_input);
+        self
+    }
+
+    /// Each text input is placed in its own action row, as required by Discord.
+    /// Inputs beyond the fifth one are dropped.
+    pub fn finish(self) -> Modal {
+        Modal {
+            custom_id: self.custom_id,
+            title: self.title,
+            components: self
+                .text_inputs
+                .into_iter()
+                .take(MAX_MODAL_COMPONENTS)
+                .map(|input| ActionRow::new().with_component(input).into())
+                .collect(),
+        }
+    }
+}
+
+#[derive(Serialize_repr, Deserialize_repr, Debug, Eq, PartialEq, Clone, Copy, Default)]
+#[repr(u8)]
+pub enum TextInputStyle {
+    #[default]
+    Short = 1,
+    Paragraph = 2,
+}
+
+/// Text input of a modal. Submitted modals contain only `custom_id` and `value` of inputs.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TextInput {
+    pub custom_id: String,
+    #[serde(default)]
+    pub style: TextInputStyle,
+    #[serde(default)]
+    pub label: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_length: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_length: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub required: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub placeholder: Option<String>,
+}
+
+impl TextInput {
+    fn new(style: TextInputStyle, custom_id: &str, label: &str) -> Self {
+        TextInput {
+            custom_id: custom_id.to_owned(),
+            style,
+            label: label.to_owned(),
+            min_length: None,
+            max_length: None,
+            required: None,
+            value: None,
+            placeholder: None,
+        }
+    }
+
+    pub fn short(custom_id: &str, label: &str) -> Self {
+        Self::new(TextInputStyle::Short, custom_id, label)
+    }
+
+    pub fn paragraph(custom_id: &str, label: &str) -> Self {
+        Self::new(TextInputStyle::Paragraph, custom_id, label)
+    }
+
+    pub fn with_value(mut self, value: &str) -> Self {
+        self.value = Some(value.to_owned());
+        self
+    }
+
+    pub fn with_placeholder(mut self, placeholder: &str) -> Self {
+        self.placeholder = Some(placeholder.to_owned());
+        self
+    }
+
+    pub fn with_length(mut self, min_length: u16, max_length: u16) -> Self {
+        self.min_length = Some(min_length);
+        self.max_length = Some(max_length);
+        self
+    }
+
+    pub fn required(mut self) -> Self {
+        self.required = Some(true);
+        self
+    }
+
+    #[allow(dead_code)]
+    pub fn not_required(mut self) -> Self {
+        self.required = Some(false);
+        self
+    }
+}
+
+impl From<TextInput> for MessageComponent {
+    fn from(text_input: TextInput) -> Self {
+        MessageComponent::TextInput(text_input)
+    }
+}
+
+/// Values of text inputs of a submitted modal, indexed by input `custom_id`
+#[derive(Debug, Default)]
+pub struct ModalSubmitValues(HashMap<String, String>);
+
+impl ModalSubmitValues {
+    pub fn from_data(interaction_data: &InteractionData) -> Self {
+        fn collect(components: &[MessageComponent], values: &mut HashMap<String, String>) {
+            for component in components {
+                match component {
+                    MessageComponent::ActionRow(row) => collect(&row.components, values),
+                    MessageComponent::TextInput(TextInput {
+                        custom_id,
+                        value: Some(value),
+                        ..
+                    }) => {
+                        values.insert(custom_id.clone(), value.clone());
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        let mut values = HashMap::new();
+        if let Some(components) = &interaction_data.components {
+            collect(components, &mut values);
+        }
+        ModalSubmitValues(values)
+    }
+
+    /// Submitted value of the input. Empty optional inputs are treated as missing.
+    pub fn get(&self, custom_id: &str) -> Option<&str> {
+        self.0
+            .get(custom_id)
+            .map(String::as_str)
+            .filter(|v| !v.is_empty())
+    }
+
+    #[allow(dead_code)]
+    pub fn parse<T: FromStr>(&self, custom_id: &str) -> Option<T> {
+        self.get(custom_id).and_then(|v| v.trim().parse().ok())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Modal, ModalSubmitValues, TextInput};
+    use crate::discord::interaction::InteractionData;
+    use serde_json::json;
+
+    #[test]
+    fn serialize_modal() {
+        let modal = Modal::build("set", "New note")
+            .with_text_input(TextInput::short("key", "Key").required())
+            .with_text_input(TextInput::paragraph("value", "Value").with_value("text"))
+            .finish();
+
+        assert_eq!(
+            serde_json::to_value(&modal).unwrap(),
+            json!({
+                "custom_id": "set",
+                "title": "New note",
+                "components": [
+                    {"type": 1, "components": [
+                        {"type": 4, "custom_id": "key", "style": 1, "label": "Key", "required": true}
+                    ]},
+                    {"type": 1, "components": [
+                        {"type": 4, "custom_id": "value", "style": 2, "label": "Value", "value": "text"}
+                    ]}
+                ]
+            })
+        );
+    }
+
+    #[test]
+    fn extract_submitted_values() {
+        let data: InteractionData = serde_json::from_value(json!({
+            "custom_id": "set",
+            "components": [
+                {"type": 1, "components": [{"type": 4, "custom_id": "key", "value": "greeting"}]},
+                {"type": 1, "components": [{"type": 4, "custom_id": "count", "value": " 42 "}]},
+                {"type": 1, "components": [{"type": 4, "custom_id": "comment", "value": ""}]}
+            ]
+        }))
+        .unwrap();
+
+        let values = ModalSubmitValues::from_data(&data);
+        assert_eq!(values.get("key"), Some("greeting"));
+        assert_eq!(values.parse::<u32>("count"), Some(42));
+        assert_eq!(values.get("comment"), None);
+        assert_eq!(values.get("missing"), None);
+    }
+}
@@ -0,0 +1,103 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::message::{Attachment, Message};
+use super::user::{GuildMember, User};
+use crate::discord::{ChannelType, Permissions, Snowflake};
+
+/// Full objects for ids referenced in options or `target_id` of an interaction
+#[derive(Deserialize, Debug, Clone, Default)]
+pub struct ResolvedData {
+    #[serde(default)]
+    pub users: HashMap<Snowflake, User>,
+    #[serde(default)]
+    pub members: HashMap<Snowflake, GuildMember>,
+    #[serde(default)]
+    #[allow(dead_code)]
+    pub roles: HashMap<Snowflake, Role>,
+    #[serde(default)]
+    #[allow(dead_code)]
+    pub channels: HashMap<Snowflake, PartialChannel>,
+    #[serde(default)]
+    pub messages: HashMap<Snowflake, Message>,
+    #[serde(default)]
+    pub attachments: HashMap<Snowflake, Attachment>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Role {
+    pub id: Snowflake,
+    pub name: String,
+    #[serde(default)]
+    pub color: u32,
+    #[serde(default)]
+    pub hoist: bool,
+    pub icon: Option<String>,
+    pub unicode_emoji: Option<String>,
+    #[serde(default)]
+    pub position: i32,
+    pub permissions: Permissions,
+    #[serde(default)]
+    pub managed: bool,
+    #[serde(default)]
+    pub mentionable: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct PartialChannel {
+    pub id: Snowflake,
+    pub name: Option<String>,
+    #[serde(rename = "type")]
+    pub channel_type: ChannelType,
+    pub permissions: Option<Permissions>,
+    pub parent_id: Option<Snowflake>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ResolvedData;
+    use crate::discord::{PermissionsProvider, Snowflake};
+    use serde_json::json;
+
+    #[test]
+    fn deserialize_resolved_maps() {
+        let resolved: ResolvedData = serde_json::from_value(json!({
+            "users": {"80351110224678912": {
+                "id": "80351110224678912",
+                "username": "nelly",
+                "discriminator": "0",
+                "global_name": "Nelly",
+                "avatar": null
+            }},
+            "members": {"80351110224678912": {
+                "nick": "nel",
+                "roles": ["41771983423143936"],
+                "joined_at": "2015-04-26T06:26:56.936000+00:00",
+                "permissions": "2048"
+            }},
+            "roles": {"41771983423143936": {
+                "id": "41771983423143936",
+                "name": "WE DEM BOYZZ!!!!!!",
+                "color": 3447003,
+                "permissions": "66321471"
+            }},
+            "channels": {"41771983423143937": {
+                "id": "41771983423143937",
+                "name": "general",
+                "type": 0,
+                "permissions": "17179869183"
+            }}
+        }))
+        .unwrap();
+
+        let user_id: Snowflake = "80351110224678912".parse().unwrap();
+        assert_eq!(resolved.users[&user_id].display_name(), "Nelly");
+        let member = &resolved.members[&user_id];
+        assert_eq!(member.nick.as_deref(), Some("nel"));
+        assert!(member.permissions.as_ref().unwrap().allowed_to_send_messages());
+        assert_eq!(resolved.roles.len(), 1);
+        assert_eq!(resolved.channels.len(), 1);
+        assert!(resolved.messages.is_empty());
+    }
+}
@@ -0,0 +1,131 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::discord::{Permissions, Snowflake};
+
+#[allow(dead_code)]
+const CDN_URL: &str = "https://cdn.discordapp.com";
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct User {
+    pub id: Snowflake,
+    pub username: String,
+    /// "0" for users migrated to unique usernames
+    #[serde(default)]
+    pub discriminator: String,
+    pub global_name: Option<String>,
+    pub avatar: Option<String>,
+    #[serde(default)]
+    pub bot: bool,
+    #[serde(default)]
+    pub system: bool,
+    pub banner: Option<String>,
+    pub accent_color: Option<u32>,
+    pub locale: Option<String>,
+    pub public_flags: Option<u64>,
+}
+
+impl User {
+    /// Name shown in the client: global display name if set, username otherwise
+    #[allow(dead_code)]
+    pub fn display_name(&self) -> &str {
+        self.global_name.as_deref().unwrap_or(&self.username)
+    }
+
+    #[allow(dead_code)]
+    pub fn mention(&self) -> String {
+        format!("<@{}>", self.id)
+    }
+
+    #[allow(dead_code)]
+    pub fn avatar_url(&self) -> Option<String> {
+        self.avatar
+            .as_ref()
+            .map(|hash| format!("{}/avatars/{}/{}.png", CDN_URL, self.id, hash))
+    }
+}
+
+/// Member of a guild. Members from resolved data are partial: they have no `user`,
+/// `deaf` and `mute`.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct GuildMember {
+    pub user: Option<User>,
+    pub nick: Option<String>,
+    pub avatar: Option<String>,
+    #[serde(default)]
+    pub roles: Vec<Snowflake>,
+    pub joined_at: Option<DateTime<Utc>>,
+    pub premium_since: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub deaf: bool,
+    #[serde(default)]
+    pub mute: bool,
+    #[serde(default)]
+    pub pending: bool,
+    /// Total permissions of the member in the channel, including overwrites.
+    /// Present only in interactions.
+    pub permissions: Option<Permissions>,
+    pub communication_disabled_until: Option<DateTime<Utc>>,
+}
+
+impl GuildMember {
+    /// Guild nickname, falling back to the user's display name
+    #[allow(dead_code)]
+    pub fn display_name(&self) -> Option<&str> {
+        self.nick
+            .as_deref()
+            .or_else(|| self.user.as_ref().map(User::display_name))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::discord::interaction::Interaction;
+    use serde_json::{json, Value};
+
+    fn interaction(extra: Value) -> Interaction {
+        let mut value = json!({
+            "application_id": "1",
+            "id": "2",
+            "token": "token",
+            "type": 2,
+            "version": 1
+        });
+        value.as_object_mut().unwrap().extend(extra.as_object().unwrap().clone());
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn invoking_user_in_guild() {
+        let interaction = interaction(json!({
+            "guild_id": "3",
+            "member": {
+                "user": {"id": "4", "username": "guild_user"},
+                "roles": [],
+                "joined_at": "2022-06-01T12:00:00+00:00",
+                "deaf": false,
+                "mute": false,
+                "permissions": "0"
+            }
+        }));
+        assert_eq!(interaction.invoking_user().unwrap().username, "guild_user");
+        assert_eq!(
+            interaction.member.unwrap().display_name(),
+            Some("guild_user")
+        );
+    }
+
+    #[test]
+    fn invoking_user_in_dm() {
+        let interaction = interaction(json!({
+            "user": {"id": "4", "username": "dm_user", "global_name": "DM User", "avatar": "abc"}
+        }));
+        let user = interaction.invoking_user().unwrap();
+        assert_eq!(user.display_name(), "DM User");
+        assert_eq!(user.mention(), "<@4>");
+        assert_eq!(
+            user.avatar_url().as_deref(),
+            Some("https://cdn.discordapp.com/avatars/4/abc.png")
+        );
+    }
+}
@@ -1,107 +1,213 @@
-use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
 
-#[derive(Debug, Deserialize, Serialize, Hash, Eq, PartialEq, Clone)]
-pub struct Locale(String);
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// Language of the Discord client, see <https://discord.com/developers/docs/reference#locales>.
+///
+/// Codes Discord adds later deserialize into [`Locale::Unknown`] instead of failing.
+#[derive(Debug, Hash, Eq, PartialEq, Clone)]
+pub enum Locale {
+    Indonesian,
+    Danish,
+    German,
+    EnglishUk,
+    EnglishUs,
+    Spanish,
+    SpanishLatam,
+    French,
+    Croatian,
+    Italian,
+    Lithuanian,
+    Hungarian,
+    Dutch,
+    Norwegian,
+    Polish,
+    PortugueseBrazilian,
+    Romanian,
+    Finnish,
+    Swedish,
+    Vietnamese,
+    Turkish,
+    Czech,
+    Greek,
+    Bulgarian,
+    Russian,
+    Ukrainian,
+    Hindi,
+    Thai,
+    ChineseChina,
+    Japanese,
+    ChineseTaiwan,
+    Korean,
+    Unknown(String),
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error("Unknown locale '{}'", .0)]
+pub struct UnknownLocale(String);
 
 impl Locale {
+    /// Every locale Discord supports
+    pub const ALL: [Locale; 32] = [
+        Locale::Indonesian,
+        Locale::Danish,
+        Locale::German,
+        Locale::EnglishUk,
+        Locale::EnglishUs,
+        Locale::Spanish,
+        Locale::SpanishLatam,
+        Locale::French,
+        Locale::Croatian,
+        Locale::Italian,
+        Locale::Lithuanian,
+        Locale::Hungarian,
+        Locale::Dutch,
+        Locale::Norwegian,
+        Locale::Polish,
+        Locale::PortugueseBrazilian,
+        Locale::Romanian,
+        Locale::Finnish,
+        Locale::Swedish,
+        Locale::Vietnamese,
+        Locale::Turkish,
+        Locale::Czech,
+        Locale::Greek,
+        Locale::Bulgarian,
+        Locale::Russian,
+        Locale::Ukrainian,
+        Locale::Hindi,
+        Locale::Thai,
+        Locale::ChineseChina,
+        Locale::Japanese,
+        Locale::ChineseTaiwan,
+        Locale::Korean,
+    ];
+
     pub fn code(&self) -> &str {
-        &self.0
+        match self {
+            Locale::Indonesian => "id",
+            Locale::Danish => "da",
+            Locale::German => "de",
+            Locale::EnglishUk => "en-GB",
+            Locale::EnglishUs => "en-US",
+            Locale::Spanish => "es-ES",
+            Locale::SpanishLatam => "es-419",
+            Locale::French => "fr",
+            Locale::Croatian => "hr",
+            Locale::Italian => "it",
+            Locale::Lithuanian => "lt",
+            Locale::Hungarian => "hu",
+            Locale::Dutch => "nl",
+            Locale::Norwegian => "no",
+            Locale::Polish => "pl",
+            Locale::PortugueseBrazilian => "pt-BR",
+            Locale::Romanian => "ro",
+            Locale::Finnish => "fi",
+            Locale::Swedish => "sv-SE",
+            Locale::Vietnamese => "vi",
+            Locale::Turkish => "tr",
+            Locale::Czech => "cs",
+            Locale::Greek => "el",
+            Locale::Bulgarian => "bg",
+            Locale::Russian => "ru",
+            Locale::Ukrainian => "uk",
+            Locale::Hindi => "hi",
+            Locale::Thai => "th",
+            Locale::ChineseChina => "zh-CN",
+            Locale::Japanese => "ja",
+            Locale::ChineseTaiwan => "zh-TW",
+            Locale::Korean => "ko",
+            Locale::Unknown(code) => code,
+        }
     }
 
-    pub const DANISH: &'static str = "da";
-    pub fn danish() -> Self {
-        Self(Self::DANISH.to_owned())
+    /// Known locale with `code`, or [`Locale::Unknown`]
+    pub fn from_code(code: &str) -> Self {
+        Self::ALL
+            .iter()
+            .find(|locale| locale.code() == code)
+            .cloned()
+            .unwrap_or_else(|| Locale::Unknown(code.to_owned()))
     }
 
-    pub const GERMAN: &'static str = "de";
-    pub fn german() -> Self {
-        Self(Self::GERMAN.to_owned())
+    #[allow(dead_code)]
+    pub fn is_known(&self) -> bool {
+        !matches!(self, Locale::Unknown(_))
     }
 
-    pub const ENGLISH_UK: &'static str = "en-GB";
-    pub fn english_uk() -> Self {
-        Self("en-GB".to_owned())
+    /// Language part of the code, `en` for `en-GB`
+    pub fn language(&self) -> &str {
+        self.code().split('-').next().unwrap_or_default()
     }
 
-    pub fn english_us() -> Self {
-        Self("en-US".to_owned())
-    }
-    pub fn spanish() -> Self {
-        Self("es-ES".to_owned())
-    }
-    pub fn french() -> Self {
-        Self("fr".to_owned())
-    }
-    pub fn croatian() -> Self {
-        Self("hr".to_owned())
-    }
-    pub fn italian() -> Self {
-        Self("it".to_owned())
-    }
-    pub fn lithuanian() -> Self {
-        Self("lt".to_owned())
-    }
-    pub fn hungarian() -> Self {
-        Self("hu".to_owned())
-    }
-    pub fn dutch() -> Self {
-        Self("nl".to_owned())
-    }
-    pub fn norwegian() -> Self {
-        Self("no".to_owned())
-    }
-    pub fn polish() -> Self {
-        Self("pl".to_owned())
-    }
-    pub fn portuguese_brazilian() -> Self {
-        Self("pt-BR".to_owned())
-    }
-    pub fn romanian() -> Self {
-        Self("ro".to_owned())
-    }
-    pub fn finnish() -> Self {
-        Self("fi".to_owned())
-    }
-    pub fn swedish() -> Self {
-        Self("sv-SE".to_owned())
-    }
-    pub fn vietnamese() -> Self {
-        Self("vi".to_owned())
-    }
-    pub fn turkish() -> Self {
-        Self("tr".to_owned())
+    /// Closest locale of the same language, used when `self` has no translation
+    pub fn parent(&self) -> Option<Locale> {
+        match self {
+            Locale::EnglishUk => Some(Locale::EnglishUs),
+            Locale::SpanishLatam => Some(Locale::Spanish),
+            _ => None,
+        }
     }
-    pub fn czech() -> Self {
-        Self("cs".to_owned())
-    }
-    pub fn greek() -> Self {
-        Self("el".to_owned())
-    }
-    pub fn bulgarian() -> Self {
-        Self("bg".to_owned())
-    }
-    pub fn russian() -> Self {
-        Self("ru".to_owned())
-    }
-    pub fn ukrainian() -> Self {
-        Self("uk".to_owned())
+
+    /// `self` followed by its parents, nearest first
+    pub fn fallbacks(&self) -> impl Iterator<Item = Locale> {
+        std::iter::successors(Some(self.clone()), Locale::parent)
     }
-    pub fn hindi() -> Self {
-        Self("hi".to_owned())
+}
+
+/// Strict parsing, for configuration where typos should not pass
+impl FromStr for Locale {
+    type Err = UnknownLocale;
+
+    fn from_str(code: &str) -> Result<Self, Self::Err> {
+        match Locale::from_code(code) {
+            Locale::Unknown(code) => Err(UnknownLocale(code)),
+            locale => Ok(locale),
+        }
     }
-    pub fn thai() -> Self {
-        Self("th".to_owned())
+}
+
+impl Display for Locale {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.code())
     }
-    pub fn chinese_china() -> Self {
-        Self("zh-CN".to_owned())
+}
+
+impl Serialize for Locale {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.code())
     }
-    pub fn japanese() -> Self {
-        Self("ja".to_owned())
+}
+
+impl<'de> Deserialize<'de> for Locale {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let code = String::deserialize(deserializer)?;
+        Ok(Locale::from_code(&code))
     }
-    pub fn chinese_taiwan() -> Self {
-        Self("zh-TW".to_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Locale;
+
+    #[test]
+    fn codes_round_trip() {
+        for locale in Locale::ALL {
+            let json = serde_json::to_string(&locale).unwrap();
+            assert_eq!(serde_json::from_str::<Locale>(&json).unwrap(), locale);
+        }
+        let unknown: Locale = serde_json::from_str("\"en_US\"").unwrap();
+        assert_eq!(unknown, Locale::Unknown(String::from("en_US")));
+        assert!("en_US".parse::<Locale>().is_err());
+        assert_eq!(serde_json::to_string(&unknown).unwrap(), "\"en_US\"");
     }
-    pub fn korean() -> Self {
-        Self("ko".to_owned())
+
+    #[test]
+    fn british_english_falls_back_to_american() {
+        let chain: Vec<Locale> = Locale::EnglishUk.fallbacks().collect();
+        assert_eq!(chain, [Locale::EnglishUk, Locale::EnglishUs]);
+        assert_eq!(Locale::SpanishLatam.language(), "es");
+        assert!(Locale::ChineseTaiwan.parent().is_none());
     }
 }
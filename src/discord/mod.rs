@@ -5,6 +5,7 @@ pub mod interaction;
 pub mod voice;
 pub mod gateway;
 pub mod rest;
+pub mod multipart;
 
 mod permissions;
 mod snowflake;
@@ -31,6 +32,7 @@ pub enum ChannelType {
     GuildStageVoice = 13,
     GuildDirectory = 14,
     GuildForum = 15,
+    GuildMedia = 16,
 }
 
 
@@ -0,0 +1,101 @@
+use std::fmt::Write;
+
+use serde::Serialize;
+
+use crate::discord::interaction::message::FileUpload;
+
+/// Body of a `multipart/form-data` request: JSON payload in `payload_json`
+/// followed by files in `files[n]` parts, as Discord expects for uploads
+pub struct MultipartBody {
+    pub content_type: String,
+    pub body: Vec<u8>,
+}
+
+impl MultipartBody {
+    pub fn encode<T: Serialize>(payload: &T, files: &[FileUpload]) -> Result<Self, serde_json::Error> {
+        let payload = serde_json::to_vec(payload)?;
+        let boundary = boundary_for(&payload, files);
+
+        let mut body = Vec::new();
+        write_part(
+            &mut body,
+            &boundary,
+            "Content-Disposition: form-data; name=\"payload_json\"\r\nContent-Type: application/json",
+            &payload,
+        );
+        for (index, file) in files.iter().enumerate() {
+            let mut headers = String::new();
+            let _ = write!(
+                headers,
+                "Content-Disposition: form-data; name=\"files[{}]\"; filename=\"{}\"\r\nContent-Type: {}",
+                index,
+                escape_quotes(&file.filename),
+                file.content_type
+            );
+            write_part(&mut body, &boundary, &headers, &file.data);
+        }
+        body.extend_from_slice(format!("--{}--\r\n", boundary).as_bytes());
+
+        Ok(MultipartBody {
+            content_type: format!("multipart/form-data; boundary={}", boundary),
+            body,
+        })
+    }
+}
+
+fn write_part(body: &mut Vec<u8>, boundary: &str, headers: &str, content: &[u8]) {
+    body.extend_from_slice(format!("--{}\r\n{}\r\n\r\n", boundary, headers).as_bytes());
+    body.extend_from_slice(content);
+    body.extend_from_slice(b"\r\n");
+}
+
+/// Boundary which occurs in none of the parts
+fn boundary_for(payload: &[u8], files: &[FileUpload]) -> String {
+    let seed = chrono::Utc::now().timestamp_nanos();
+    (0..)
+        .map(|attempt: u32| format!("discord-bot-{:x}-{}", seed, attempt))
+        .find(|boundary| {
+            std::iter::once(payload)
+                .chain(files.iter().map(|f| f.data.as_slice()))
+                .all(|part| !contains(part, boundary.as_bytes()))
+        })
+        .expect("boundary")
+}
+
+fn contains(haystack: &[u8], needle: &[u8]) -> bool {
+    haystack.windows(needle.len()).any(|window| window == needle)
+}
+
+fn escape_quotes(filename: &str) -> String {
+    filename.replace('"', "%22").replace(['\r', '\n'], "")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MultipartBody;
+    use crate::discord::interaction::message::FileUpload;
+    use serde_json::json;
+
+    #[test]
+    fn encode_payload_and_files() {
+        let files = [FileUpload::new("note.txt", "text/plain", b"hello".to_vec())];
+        let multipart = MultipartBody::encode(&json!({"content": "see file"}), &files).unwrap();
+
+        let boundary = multipart
+            .content_type
+            .strip_prefix("multipart/form-data; boundary=")
+            .unwrap();
+        let body = String::from_utf8(multipart.body).unwrap();
+        assert_eq!(
+            body,
+            format!(
+                "--{b}\r\nContent-Disposition: form-data; name=\"payload_json\"\r\nContent-Type: application/json\r\n\r\n\
+                 {{\"content\":\"see file\"}}\r\n\
+                 --{b}\r\nContent-Disposition: form-data; name=\"files[0]\"; filename=\"note.txt\"\r\nContent-Type: text/plain\r\n\r\n\
+                 hello\r\n\
+                 --{b}--\r\n",
+                b = boundary
+            )
+        );
+    }
+}
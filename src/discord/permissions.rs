@@ -1,12 +1,90 @@
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Serialize, Serializer};
 
 type Inner = i64;
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Deserialize, Clone, Eq, PartialEq)]
 pub struct Permissions(
     #[serde(deserialize_with = "serde_aux::prelude::deserialize_number_from_string")] Inner,
 );
 
+/// Discord sends and expects permissions as strings, they do not fit into JSON numbers
+impl Serialize for Permissions {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.0.to_string())
+    }
+}
+
+/// Names of permissions as shown in the client, indexed by bit
+const NAMES: [&str; 41] = [
+    "Create Invite",
+    "Kick Members",
+    "Ban Members",
+    "Administrator",
+    "Manage Channels",
+    "Manage Server",
+    "Add Reactions",
+    "View Audit Log",
+    "Priority Speaker",
+    "Video",
+    "View Channel",
+    "Send Messages",
+    "Send Text-to-Speech Messages",
+    "Manage Messages",
+    "Embed Links",
+    "Attach Files",
+    "Read Message History",
+    "Mention @everyone, @here, and All Roles",
+    "Use External Emoji",
+    "View Server Insights",
+    "Connect",
+    "Speak",
+    "Mute Members",
+    "Deafen Members",
+    "Move Members",
+    "Use Voice Activity",
+    "Change Nickname",
+    "Manage Nicknames",
+    "Manage Roles",
+    "Manage Webhooks",
+    "Manage Emojis and Stickers",
+    "Use Application Commands",
+    "Request to Speak",
+    "Manage Events",
+    "Manage Threads",
+    "Create Public Threads",
+    "Create Private Threads",
+    "Use External Stickers",
+    "Send Messages in Threads",
+    "Use Activities",
+    "Timeout Members",
+];
+
+impl Permissions {
+    pub fn none() -> Self {
+        Permissions(0)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    /// Permissions from `required` which are not granted
+    pub fn missing(&self, required: &Permissions) -> Permissions {
+        Permissions(required.0 & !self.0)
+    }
+
+    /// Names of granted permissions, unknown bits are skipped
+    pub fn names(&self) -> Vec<&'static str> {
+        NAMES
+            .iter()
+            .enumerate()
+            .filter(|(bit, _)| is_flag_set(self.0, *bit as Inner))
+            .map(|(_, name)| *name)
+            .collect()
+    }
+}
+
+#[allow(dead_code)]
 pub trait PermissionsProvider {
     fn number_string(&self) -> String;
     fn number(&self) -> Inner;
@@ -415,7 +493,7 @@ impl Default for PermissionsMut {
     }
 }
 
-#[allow(clippy::useless_conversion)]
+#[allow(clippy::useless_conversion, dead_code)]
 impl PermissionsMut {
     pub fn new() -> Self {
         PermissionsMut(0.into())
@@ -7,9 +7,19 @@ use crate::discord::snowflake::Snowflake;
 
 use crate::discord::{permissions::Permissions, ChannelType, Locale};
 
-#[derive(Debug, Serialize_repr, Deserialize_repr, Eq, PartialEq, Clone, Copy)]
+/// Kind of command: slash command or entry of user/message context menu
+#[derive(Debug, Serialize_repr, Deserialize_repr, Eq, PartialEq, Hash, Clone, Copy, Default)]
 #[repr(u8)]
 pub enum ApplicationCommandType {
+    #[default]
+    ChatInput = 1,
+    User = 2,
+    Message = 3,
+}
+
+#[derive(Debug, Serialize_repr, Deserialize_repr, Eq, PartialEq, Clone, Copy)]
+#[repr(u8)]
+pub enum ApplicationCommandOptionType {
     SubCommand = 1,
     SubCommandGroup = 2,
     String = 3,
@@ -34,11 +44,35 @@ pub struct ApplicationCommandOptionChoice {
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(untagged)]
 pub enum ApplicationCommandOptionValue {
+    Boolean(bool),
     Str(String),
     Integer(i64),
     Double(f64),
 }
 
+impl ApplicationCommandOptionValue {
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            ApplicationCommandOptionValue::Str(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            ApplicationCommandOptionValue::Boolean(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub fn as_i64(&self) -> Option<i64> {
+        match self {
+            ApplicationCommandOptionValue::Integer(value) => Some(*value),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ApplicationCommand {
     pub id: Snowflake,
@@ -64,8 +98,110 @@ impl ApplicationCommand {
     pub fn build_for_application(
         command_name: &str,
         application_id: Snowflake,
-    ) -> ApplicationCommandBuilder {
+    ) -> ApplicationCommandBuilder<'_> {
+        ApplicationCommandBuilder::for_application(command_name, application_id)
+    }
+
+    /// Entry in the context menu of users. Such commands have no description and options.
+    pub fn build_user_command(
+        command_name: &str,
+        application_id: Snowflake,
+    ) -> ApplicationCommandBuilder<'_> {
+        ApplicationCommandBuilder::for_application(command_name, application_id)
+            .with_type(ApplicationCommandType::User)
+    }
+
+    /// Entry in the context menu of messages. Such commands have no description and options.
+    pub fn build_message_command(
+        command_name: &str,
+        application_id: Snowflake,
+    ) -> ApplicationCommandBuilder<'_> {
         ApplicationCommandBuilder::for_application(command_name, application_id)
+            .with_type(ApplicationCommandType::Message)
+    }
+}
+
+/// Name or description Discord would reject, `locale` is `None` for the default one
+#[derive(Debug, thiserror::Error)]
+pub enum CommandValidationError {
+    #[error("Invalid name '{}' ({:?}) of command '{}'", name, locale, command)]
+    Name { command: String, locale: Option<Locale>, name: String },
+    #[error("Invalid description '{}' ({:?}) of command '{}'", description, locale, command)]
+    Description { command: String, locale: Option<Locale>, description: String },
+}
+
+const NAME_LENGTH: std::ops::RangeInclusive<usize> = 1..=32;
+const DESCRIPTION_LENGTH: std::ops::RangeInclusive<usize> = 1..=100;
+
+/// Names of slash commands and options: lowercase letters, digits, `-` and `_`
+fn is_valid_chat_input_name(name: &str) -> bool {
+    NAME_LENGTH.contains(&name.chars().count())
+        && name.chars().all(|c| (c.is_alphanumeric() && !c.is_uppercase()) || c == '-' || c == '_')
+}
+
+/// Names of context menu entries may contain anything, including spaces
+fn is_valid_context_menu_name(name: &str) -> bool {
+    NAME_LENGTH.contains(&name.chars().count()) && name.trim() == name
+}
+
+fn is_valid_description(description: &str) -> bool {
+    DESCRIPTION_LENGTH.contains(&description.chars().count())
+}
+
+/// Default value and its localizations, the default one keyed by `None`
+fn localized<'a>(
+    default: &'a str,
+    localizations: &'a Option<HashMap<Locale, String>>,
+) -> impl Iterator<Item = (Option<&'a Locale>, &'a str)> {
+    std::iter::once((None, default)).chain(
+        localizations
+            .iter()
+            .flatten()
+            .map(|(locale, value)| (Some(locale), value.as_str())),
+    )
+}
+
+impl ApplicationCommand {
+    /// Checks names and descriptions of the command and its options in every locale
+    pub fn validate(&self) -> Result<(), CommandValidationError> {
+        let chat_input = self.command_type.unwrap_or_default() == ApplicationCommandType::ChatInput;
+        let name_error = |locale: Option<&Locale>, name: &str| CommandValidationError::Name {
+            command: self.name.clone(),
+            locale: locale.cloned(),
+            name: name.to_owned(),
+        };
+        let description_error = |locale: Option<&Locale>, description: &str| CommandValidationError::Description {
+            command: self.name.clone(),
+            locale: locale.cloned(),
+            description: description.to_owned(),
+        };
+        for (locale, name) in localized(&self.name, &self.name_localizations) {
+            let valid = if chat_input { is_valid_chat_input_name(name) } else { is_valid_context_menu_name(name) };
+            if !valid {
+                return Err(name_error(locale, name));
+            }
+        }
+        if !chat_input {
+            return Ok(());
+        }
+        for (locale, description) in localized(&self.description, &self.description_localizations) {
+            if !is_valid_description(description) {
+                return Err(description_error(locale, description));
+            }
+        }
+        for option in self.options.iter().flat_map(|options| options.iter()) {
+            for (locale, name) in localized(&option.name, &option.name_localizations) {
+                if !is_valid_chat_input_name(name) {
+                    return Err(name_error(locale, name));
+                }
+            }
+            for (locale, description) in localized(&option.description, &option.description_localizations) {
+                if !is_valid_description(description) {
+                    return Err(description_error(locale, description));
+                }
+            }
+        }
+        Ok(())
     }
 }
 
@@ -76,7 +212,7 @@ pub struct ApplicationCommandBuilder<'builder> {
     guild_id: Option<Snowflake>,
     name: &'builder str,
     name_localizations: Option<HashMap<Locale, String>>,
-    description: &'builder str,
+    description: String,
     description_localizations: Option<HashMap<Locale, String>>,
     options: Option<Vec<ApplicationCommandOption>>,
     default_member_permissions: Option<Permissions>,
@@ -96,7 +232,7 @@ impl<'builder> ApplicationCommandBuilder<'builder> {
             guild_id: None,
             name,
             name_localizations: None,
-            description: "",
+            description: String::new(),
             description_localizations: None,
             options: None,
             default_member_permissions: None,
@@ -112,15 +248,34 @@ impl<'builder> ApplicationCommandBuilder<'builder> {
         }
         self
     }
+    /// Only members with all of `permissions` see the command, unless guild admins override it.
+    /// Empty permissions leave the command available to everyone.
+    pub fn with_default_member_permissions(mut self, permissions: Permissions) -> Self {
+        self.default_member_permissions = Some(permissions).filter(|p| !p.is_empty());
+        self
+    }
+    pub fn with_type(mut self, command_type: ApplicationCommandType) -> Self {
+        self.command_type = Some(command_type);
+        self
+    }
     pub fn with_description(
         self,
-        description: &'builder str,
+        description: &str,
     ) -> ApplicationCommandBuilder<'builder> {
         ApplicationCommandBuilder {
-            description,
+            description: description.to_owned(),
             ..self
         }
     }
+    /// Names shown to users of other locales, Discord still sends the default name
+    pub fn with_name_localizations(mut self, localizations: HashMap<Locale, String>) -> Self {
+        self.name_localizations = Some(localizations).filter(|l| !l.is_empty());
+        self
+    }
+    pub fn with_description_localizations(mut self, localizations: HashMap<Locale, String>) -> Self {
+        self.description_localizations = Some(localizations).filter(|l| !l.is_empty());
+        self
+    }
     pub fn finish(self) -> ApplicationCommand {
         ApplicationCommand {
             id: self.id,
@@ -129,7 +284,7 @@ impl<'builder> ApplicationCommandBuilder<'builder> {
             guild_id: self.guild_id,
             name: self.name.to_string(),
             name_localizations: self.name_localizations,
-            description: self.description.to_string(),
+            description: self.description,
             description_localizations: self.description_localizations,
             options: self.options.map(Vec::into_boxed_slice),
             default_member_permissions: self.default_member_permissions,
@@ -142,7 +297,7 @@ impl<'builder> ApplicationCommandBuilder<'builder> {
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct ApplicationCommandOption {
     #[serde(rename = "type")]
-    pub command_type: ApplicationCommandType,
+    pub option_type: ApplicationCommandOptionType,
     pub name: String,
     pub name_localizations: Option<HashMap<Locale, String>>,
     pub description: String,
@@ -157,16 +312,29 @@ pub struct ApplicationCommandOption {
 }
 
 impl ApplicationCommandOption {
-    pub fn build_string_option(name: &str) -> ApplicationCommandOptionBuilder {
+    pub fn build_string_option(name: &str) -> ApplicationCommandOptionBuilder<'_> {
         ApplicationCommandOptionBuilder::string_option(name)
     }
+
+    pub fn build_boolean_option(name: &str) -> ApplicationCommandOptionBuilder<'_> {
+        ApplicationCommandOptionBuilder::boolean_option(name)
+    }
+
+    pub fn build_integer_option(name: &str) -> ApplicationCommandOptionBuilder<'_> {
+        ApplicationCommandOptionBuilder::integer_option(name)
+    }
+
+    /// Option for an uploaded file. Its value is the id of the attachment in resolved data.
+    pub fn build_attachment_option(name: &str) -> ApplicationCommandOptionBuilder<'_> {
+        ApplicationCommandOptionBuilder::attachment_option(name)
+    }
 }
 
 pub struct ApplicationCommandOptionBuilder<'builder> {
-    command_type: ApplicationCommandType,
+    option_type: ApplicationCommandOptionType,
     name: &'builder str,
     name_localizations: Option<HashMap<Locale, String>>,
-    description: &'builder str,
+    description: String,
     description_localizations: Option<HashMap<Locale, String>>,
     required: Option<bool>,
     choices: Option<Vec<ApplicationCommandOptionChoice>>,
@@ -179,10 +347,29 @@ pub struct ApplicationCommandOptionBuilder<'builder> {
 
 impl<'builder> ApplicationCommandOptionBuilder<'builder> {
     pub fn string_option(name: &'builder str) -> ApplicationCommandOptionBuilder<'builder> {
+        Self::new(ApplicationCommandOptionType::String, name)
+    }
+
+    pub fn boolean_option(name: &'builder str) -> ApplicationCommandOptionBuilder<'builder> {
+        Self::new(ApplicationCommandOptionType::Boolean, name)
+    }
+
+    pub fn integer_option(name: &'builder str) -> ApplicationCommandOptionBuilder<'builder> {
+        Self::new(ApplicationCommandOptionType::Integer, name)
+    }
+
+    pub fn attachment_option(name: &'builder str) -> ApplicationCommandOptionBuilder<'builder> {
+        Self::new(ApplicationCommandOptionType::Attachment, name)
+    }
+
+    fn new(
+        option_type: ApplicationCommandOptionType,
+        name: &'builder str,
+    ) -> ApplicationCommandOptionBuilder<'builder> {
         ApplicationCommandOptionBuilder {
-            command_type: ApplicationCommandType::String,
+            option_type,
             name,
-            description: "",
+            description: String::new(),
             description_localizations: None,
             required: None,
             choices: None,
@@ -196,13 +383,41 @@ impl<'builder> ApplicationCommandOptionBuilder<'builder> {
     }
     pub fn with_description(
         self,
-        description: &'builder str,
+        description: &str,
     ) -> ApplicationCommandOptionBuilder<'builder> {
         ApplicationCommandOptionBuilder {
-            description,
+            description: description.to_owned(),
             ..self
         }
     }
+    pub fn with_name_localizations(mut self, localizations: HashMap<Locale, String>) -> Self {
+        self.name_localizations = Some(localizations).filter(|l| !l.is_empty());
+        self
+    }
+    pub fn with_description_localizations(mut self, localizations: HashMap<Locale, String>) -> Self {
+        self.description_localizations = Some(localizations).filter(|l| !l.is_empty());
+        self
+    }
+    /// Smallest value accepted by integer and number options
+    pub fn with_min_value(mut self, min_value: f64) -> Self {
+        self.min_value = Some(min_value);
+        self
+    }
+
+    /// Adds a predefined value users pick from, shown as `name`
+    pub fn with_choice(
+        mut self,
+        name: &str,
+        value: ApplicationCommandOptionValue,
+        name_localizations: HashMap<Locale, String>,
+    ) -> Self {
+        self.choices.get_or_insert_with(Vec::new).push(ApplicationCommandOptionChoice {
+            name: name.to_owned(),
+            name_localizations: Some(name_localizations).filter(|l| !l.is_empty()),
+            value,
+        });
+        self
+    }
     pub fn required(mut self) -> Self {
         self.required = Some(true);
         self
@@ -215,10 +430,10 @@ impl<'builder> ApplicationCommandOptionBuilder<'builder> {
 
     pub fn finish(self) -> ApplicationCommandOption {
         ApplicationCommandOption {
-            command_type: self.command_type,
+            option_type: self.option_type,
             name: self.name.to_string(),
             name_localizations: self.name_localizations,
-            description: self.description.to_string(),
+            description: self.description,
             description_localizations: self.description_localizations,
             required: self.required,
             choices: self.choices.map(Vec::into_boxed_slice),
@@ -229,4 +444,41 @@ impl<'builder> ApplicationCommandOptionBuilder<'builder> {
             autocomplete: self.autocomplete,
         }
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::{ApplicationCommand, ApplicationCommandOption, CommandValidationError};
+    use crate::discord::{Locale, Snowflake};
+
+    #[test]
+    fn localized_names_are_validated() {
+        let option = ApplicationCommandOption::build_string_option("key")
+            .with_description("Key of note")
+            .with_name_localizations(HashMap::from([(Locale::German, String::from("schlüssel"))]))
+            .finish();
+        let command = ApplicationCommand::build_for_application("get", Snowflake::default())
+            .with_description("Read saved note")
+            .with_option(option.clone())
+            .finish();
+        assert!(command.validate().is_ok());
+
+        let command = ApplicationCommand::build_for_application("get", Snowflake::default())
+            .with_description("Read saved note")
+            .with_name_localizations(HashMap::from([(Locale::French, String::from("lire note"))]))
+            .with_option(option)
+            .finish();
+        assert!(matches!(
+            command.validate(),
+            Err(CommandValidationError::Name { locale: Some(Locale::French), .. })
+        ));
+    }
+
+    #[test]
+    fn context_menu_names_may_contain_spaces() {
+        let command = ApplicationCommand::build_message_command("Save as note", Snowflake::default()).finish();
+        assert!(command.validate().is_ok());
+    }
+}
@@ -0,0 +1,92 @@
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use actix_web::web::{Bytes, Data};
+use actix_web::{web, App, HttpRequest, HttpResponse, HttpServer};
+
+use super::DiscordBotApiClient;
+use crate::Snowflake;
+
+#[derive(Debug, Clone)]
+pub struct RecordedRequest {
+    pub method: String,
+    pub path: String,
+    pub content_type: String,
+    pub body: Bytes,
+}
+
+/// Local HTTP server standing in for Discord API, records every request it receives
+pub struct MockDiscord {
+    base_url: String,
+    requests: Arc<Mutex<Vec<RecordedRequest>>>,
+}
+
+async fn record(
+    request: HttpRequest,
+    body: Bytes,
+    requests: Data<Mutex<Vec<RecordedRequest>>>,
+) -> HttpResponse {
+    let content_type = request
+        .headers()
+        .get("Content-Type")
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or_default()
+        .to_owned();
+    requests.lock().unwrap().push(RecordedRequest {
+        method: request.method().to_string(),
+        path: request.path().to_owned(),
+        content_type,
+        body,
+    });
+    HttpResponse::Ok().json(serde_json::json!({}))
+}
+
+impl MockDiscord {
+    pub fn start() -> Self {
+        let requests = Data::new(Mutex::new(vec![]));
+        let app_requests = requests.clone();
+        let server = HttpServer::new(move || {
+            App::new()
+                .app_data(app_requests.clone())
+                .default_service(web::to(record))
+        })
+        .workers(1)
+        .bind(("127.0.0.1", 0))
+        .expect("mock server address");
+        let address = server.addrs()[0];
+        actix_rt::spawn(server.run());
+        MockDiscord {
+            base_url: format!("http://{}", address),
+            requests: requests.into_inner(),
+        }
+    }
+
+    pub fn client(&self) -> DiscordBotApiClient {
+        DiscordBotApiClient::new(
+            "token",
+            &self.base_url,
+            "http://localhost",
+            "test",
+            "1".parse::<Snowflake>().unwrap(),
+        )
+    }
+
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    pub fn requests(&self) -> Vec<RecordedRequest> {
+        self.requests.lock().unwrap().clone()
+    }
+
+    /// Waits until the server received at least `count` requests
+    pub async fn wait_for_requests(&self, count: usize) -> Vec<RecordedRequest> {
+        for _ in 0..100 {
+            if self.requests.lock().unwrap().len() >= count {
+                break;
+            }
+            actix_rt::time::sleep(Duration::from_millis(20)).await;
+        }
+        self.requests()
+    }
+}
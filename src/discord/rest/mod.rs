@@ -1,17 +1,27 @@
-use reqwest::ClientBuilder;
-use reqwest::header::{HeaderMap, HeaderValue};
+use reqwest::{ClientBuilder, RequestBuilder};
+use reqwest::header::{HeaderMap, HeaderValue, CONTENT_TYPE};
+use crate::discord::interaction::message::Attachment;
+use crate::discord::interaction::InteractionCallbackMessage;
+use crate::discord::multipart::MultipartBody;
 use crate::discord::rest::application_command::ApplicationCommand;
 use crate::Snowflake;
 
 pub mod application_command;
+mod webhook;
 
+pub use webhook::InteractionWebhook;
+
+const API_VERSION: &str = "v10";
 
 #[derive(Clone)]
 pub struct DiscordBotApiClient {
+    #[allow(dead_code)]
     token: String,
     base_url: String,
     app_id: Snowflake,
     client: reqwest::Client,
+    /// Client without bot authorization, for attachments on the CDN
+    cdn_client: reqwest::Client,
 }
 
 impl DiscordBotApiClient {
@@ -56,6 +66,7 @@ impl DiscordBotApiClient {
                     .build()
                     .expect("Http client")
             },
+            cdn_client: reqwest::Client::new(),
         }
     }
 
@@ -66,7 +77,7 @@ impl DiscordBotApiClient {
     ) -> Result<ApplicationCommand, reqwest::Error> {
         let base_url = &self.base_url;
         let app_id = self.app_id;
-        let url = format!("{}/v8/applications/{}/commands", base_url, app_id);
+        let url = format!("{}/{}/applications/{}/commands", base_url, API_VERSION, app_id);
         self
             .client
             .post(url)
@@ -76,4 +87,117 @@ impl DiscordBotApiClient {
             .json::<ApplicationCommand>()
             .await
     }
+
+    fn webhook_url(&self, interaction_token: &str) -> String {
+        format!(
+            "{}/{}/webhooks/{}/{}",
+            self.base_url, API_VERSION, self.app_id, interaction_token
+        )
+    }
+
+    /// Edits the initial response of the interaction, including deferred one
+    pub async fn edit_original_interaction_response(
+        &self,
+        interaction_token: &str,
+        message: &InteractionCallbackMessage,
+    ) -> Result<(), reqwest::Error> {
+        let url = format!("{}/messages/@original", self.webhook_url(interaction_token));
+        self.client
+            .patch(url)
+            .message(message)
+            .send()
+            .await?
+            .error_for_status()?;
+        Ok(())
+    }
+
+    pub async fn delete_original_interaction_response(
+        &self,
+        interaction_token: &str,
+    ) -> Result<(), reqwest::Error> {
+        let url = format!("{}/messages/@original", self.webhook_url(interaction_token));
+        self.client.delete(url).send().await?.error_for_status()?;
+        Ok(())
+    }
+
+    /// Interaction tokens are valid for 15 minutes, so are follow-up messages
+    pub async fn create_followup_message(
+        &self,
+        interaction_token: &str,
+        message: &InteractionCallbackMessage,
+    ) -> Result<(), reqwest::Error> {
+        let url = self.webhook_url(interaction_token);
+        self.client
+            .post(url)
+            .message(message)
+            .send()
+            .await?
+            .error_for_status()?;
+        Ok(())
+    }
+
+    pub async fn create_message(
+        &self,
+        channel_id: Snowflake,
+        message: &InteractionCallbackMessage,
+    ) -> Result<(), reqwest::Error> {
+        let url = format!("{}/{}/channels/{}/messages", self.base_url, API_VERSION, channel_id);
+        self.client
+            .post(url)
+            .message(message)
+            .send()
+            .await?
+            .error_for_status()?;
+        Ok(())
+    }
+
+    pub async fn download_attachment(&self, attachment: &Attachment) -> Result<Vec<u8>, reqwest::Error> {
+        let response = self
+            .cdn_client
+            .get(&attachment.url)
+            .send()
+            .await?
+            .error_for_status()?;
+        Ok(response.bytes().await?.to_vec())
+    }
+
+    #[allow(dead_code)]
+    pub async fn edit_followup_message(
+        &self,
+        interaction_token: &str,
+        message_id: Snowflake,
+        message: &InteractionCallbackMessage,
+    ) -> Result<(), reqwest::Error> {
+        let url = format!("{}/messages/{}", self.webhook_url(interaction_token), message_id);
+        self.client
+            .patch(url)
+            .message(message)
+            .send()
+            .await?
+            .error_for_status()?;
+        Ok(())
+    }
+}
+
+trait MessageRequest {
+    fn message(self, message: &InteractionCallbackMessage) -> Self;
+}
+
+impl MessageRequest for RequestBuilder {
+    /// JSON body, or multipart body when the message has files
+    fn message(self, message: &InteractionCallbackMessage) -> Self {
+        if message.files.is_empty() {
+            return self.json(message);
+        }
+        match MultipartBody::encode(message, &message.files) {
+            Ok(multipart) => self
+                .header(CONTENT_TYPE, multipart.content_type)
+                .body(multipart.body),
+            // `json` reports the same serialization error when the request is sent
+            Err(_) => self.json(message),
+        }
+    }
 }
+
+#[cfg(test)]
+pub mod mock;
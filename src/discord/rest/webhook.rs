@@ -0,0 +1,82 @@
+use crate::discord::interaction::InteractionCallbackMessage;
+use crate::discord::rest::DiscordBotApiClient;
+use crate::Snowflake;
+
+/// Handle for messages of a single interaction, usable after the initial response was sent
+#[derive(Clone)]
+pub struct InteractionWebhook {
+    client: DiscordBotApiClient,
+    token: String,
+}
+
+impl InteractionWebhook {
+    pub fn new(client: DiscordBotApiClient, token: &str) -> Self {
+        Self {
+            client,
+            token: token.to_owned(),
+        }
+    }
+
+    pub async fn edit_original(&self, message: &InteractionCallbackMessage) -> Result<(), reqwest::Error> {
+        self.client
+            .edit_original_interaction_response(&self.token, message)
+            .await
+    }
+
+    pub async fn delete_original(&self) -> Result<(), reqwest::Error> {
+        self.client
+            .delete_original_interaction_response(&self.token)
+            .await
+    }
+
+    pub async fn followup(&self, message: &InteractionCallbackMessage) -> Result<(), reqwest::Error> {
+        self.client
+            .create_followup_message(&self.token, message)
+            .await
+    }
+
+    #[allow(dead_code)]
+    pub async fn edit_followup(
+        &self,
+        message_id: Snowflake,
+        message: &InteractionCallbackMessage,
+    ) -> Result<(), reqwest::Error> {
+        self.client
+            .edit_followup_message(&self.token, message_id, message)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::discord::interaction::message::FileUpload;
+    use crate::discord::interaction::InteractionCallbackMessage;
+    use crate::discord::rest::mock::MockDiscord;
+    use super::InteractionWebhook;
+
+    #[actix_rt::test]
+    async fn followup_with_file_is_multipart() {
+        let discord = MockDiscord::start();
+        let webhook = InteractionWebhook::new(discord.client(), "interaction-token");
+        let message = InteractionCallbackMessage::default()
+            .with_file(FileUpload::new("note.txt", "text/plain", b"note text".to_vec()));
+
+        webhook.followup(&message).await.unwrap();
+
+        let request = &discord.requests()[0];
+        assert!(request.content_type.starts_with("multipart/form-data; boundary="));
+        let body = String::from_utf8(request.body.to_vec()).unwrap();
+        assert!(body.contains(r#"{"allowed_mentions":{"parse":[]},"attachments":[{"id":0,"filename":"note.txt"}]}"#));
+        assert!(body.contains("name=\"files[0]\"; filename=\"note.txt\"\r\nContent-Type: text/plain\r\n\r\nnote text\r\n"));
+    }
+
+    #[actix_rt::test]
+    async fn followup_without_files_is_json() {
+        let discord = MockDiscord::start();
+        let webhook = InteractionWebhook::new(discord.client(), "interaction-token");
+
+        webhook.followup(&InteractionCallbackMessage::default()).await.unwrap();
+
+        assert_eq!(discord.requests()[0].content_type, "application/json");
+    }
+}
@@ -3,9 +3,10 @@ use std::{fmt::Display, num::ParseIntError, str::FromStr};
 use chrono::{DateTime, TimeZone, Utc};
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Copy, Deserialize)]
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Deserialize)]
 pub struct Snowflake(#[serde(deserialize_with = "serde_aux::prelude::deserialize_number_from_string")] i64);
 
+#[allow(dead_code)]
 pub const DISCORD_EPOCH: i64 = 1420070400000;
 
 impl Display for Snowflake {
@@ -30,36 +31,42 @@ impl Default for Snowflake {
 }
 
 impl Snowflake {
+    #[allow(dead_code)]
     fn unwrap(&self) -> i64 {
-        *&self.0
+        self.0
     }
 
     pub fn zero() -> Self {
         Snowflake(0)
     }
 
+    #[allow(dead_code)]
     pub fn timestamp(&self) -> DateTime<Utc> {
         let discord_ts = &self.unwrap() >> 22;
         let unix_ts = discord_ts + DISCORD_EPOCH;
         Utc.timestamp_millis(unix_ts)
     }
 
+    #[allow(dead_code)]
     pub fn from_timestamp(timestamp: &DateTime<Utc>) -> Self {
         let unix_ts = timestamp.timestamp_millis();
         let discord_ts = unix_ts - DISCORD_EPOCH;
         Snowflake(discord_ts << 22)
     }
 
+    #[allow(dead_code)]
     pub fn increment(&self) -> u16 {
         // Never panic
         (&self.unwrap() & 0xFFF).try_into().unwrap()
     }
 
+    #[allow(dead_code)]
     pub fn ipid(&self) -> u8 {
         // Never panic
         (&self.unwrap() & 0x1F000).try_into().unwrap()
     }
 
+    #[allow(dead_code)]
     pub fn iwid(&self) -> u8 {
         // Never panic
         (&self.unwrap() & 0x3E0000).try_into().unwrap()
@@ -1,15 +1,56 @@
+use crate::discord::interaction::Interaction;
 use crate::discord::rest::DiscordBotApiClient;
+use std::sync::Arc;
+
+use crate::discord::Snowflake;
+use crate::domain::i18n::{Catalog, Translator};
+use crate::discord::PermissionsProvider;
+use crate::domain::namespace::{Namespace, Notes, Requester};
 use crate::Storage;
 
 #[derive(Clone)]
 pub struct BotContext {
     store: Storage,
     api_client: DiscordBotApiClient,
+    catalog: Catalog,
+    /// Users who may manage global notes
+    admins: Arc<[Snowflake]>,
+    /// Speaks the default language until the context is made for an interaction
+    translator: Translator,
+    /// Only public global notes are readable until the context is made for an interaction
+    notes: Notes,
 }
 
 impl BotContext {
-    pub fn new(store: Storage, api_client: DiscordBotApiClient) -> Self {
-        Self { store, api_client }
+    pub fn new(store: Storage, api_client: DiscordBotApiClient, catalog: Catalog) -> Self {
+        let translator = Translator::new(catalog.clone(), []);
+        let notes = Notes::new(store.clone(), None, None);
+        Self { store, api_client, catalog, admins: Arc::new([]), translator, notes }
+    }
+
+    pub fn with_admins(mut self, admins: impl IntoIterator<Item = Snowflake>) -> Self {
+        self.admins = admins.into_iter().collect();
+        self
+    }
+
+    /// Copy of the context which translates into the language of `interaction`
+    /// and reaches the notes of its guild or user
+    pub fn for_interaction(&self, interaction: &Interaction) -> Self {
+        let moderator = interaction
+            .member
+            .as_ref()
+            .and_then(|member| member.permissions.as_ref())
+            .is_some_and(|granted| granted.allowed_to_manage_messages() || granted.allowed_to_administrator());
+        let requester = interaction.invoking_user().map(|user| Requester {
+            user_id: user.id,
+            admin: self.admins.contains(&user.id),
+            moderator,
+        });
+        Self {
+            translator: self.catalog.translator(interaction),
+            notes: Notes::new(self.store.clone(), Namespace::of(interaction), requester),
+            ..self.clone()
+        }
     }
 }
 
@@ -25,6 +66,24 @@ impl Get<Storage> for BotContext {
     }
 }
 
+impl Get<Notes> for BotContext {
+    fn get(&self) -> &Notes {
+        &self.notes
+    }
+}
+
+impl Get<Catalog> for BotContext {
+    fn get(&self) -> &Catalog {
+        &self.catalog
+    }
+}
+
+impl Get<Translator> for BotContext {
+    fn get(&self) -> &Translator {
+        &self.translator
+    }
+}
+
 pub trait Get<T> {
     fn get(&self) -> &T;
 }
\ No newline at end of file
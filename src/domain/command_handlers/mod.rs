@@ -1,15 +1,24 @@
-use crate::domain::interaction_pipeline::{InteractionError, InteractionHandler, InteractionHandlerResult, NoContextInteractionHandler, Task};
-use futures_util::FutureExt;
-use std::future::ready;
-use crate::discord::interaction::{Interaction, InteractionCallback, InteractionData, InteractionType};
+use crate::domain::interaction_pipeline::{InteractionError, InteractionResponse};
+use crate::discord::interaction::message::Message;
+use crate::discord::interaction::user::{GuildMember, User};
+use crate::discord::interaction::InteractionData;
+use crate::discord::Permissions;
 
-pub type CommandHandlerResult = Result<InteractionCallback, InteractionError>;
+pub type CommandHandlerResult = Result<InteractionResponse, InteractionError>;
 
 pub trait CommandHandler<Context> {
     type Args;
     type Future;
 
     fn name() -> &'static str;
+    /// Member permissions needed to use the command, also registered as its default permissions
+    fn required_permissions() -> Permissions {
+        Permissions::none()
+    }
+    /// Permissions the bot needs in the channel to carry out the command
+    fn required_bot_permissions() -> Permissions {
+        Permissions::none()
+    }
     fn parse_args(interaction_data: &InteractionData) -> Option<Self::Args>;
     fn handle(&self, args: Self::Args, context: &Context) -> Self::Future;
 }
@@ -19,6 +28,12 @@ pub trait NoContextCommandHandler {
     type Future;
 
     fn name() -> &'static str;
+    fn required_permissions() -> Permissions {
+        Permissions::none()
+    }
+    fn required_bot_permissions() -> Permissions {
+        Permissions::none()
+    }
     fn parse_args(interaction_data: &InteractionData) -> Option<Self::Args>;
     fn handle(&self, args: Self::Args) -> Self::Future;
 }
@@ -30,10 +45,54 @@ impl<T: NoContextCommandHandler, C> CommandHandler<C> for T {
     fn name() -> &'static str {
         Self::name()
     }
+    fn required_permissions() -> Permissions {
+        <Self as NoContextCommandHandler>::required_permissions()
+    }
+    fn required_bot_permissions() -> Permissions {
+        <Self as NoContextCommandHandler>::required_bot_permissions()
+    }
     fn parse_args(interaction_data: &InteractionData) -> Option<Self::Args> {
         Self::parse_args(interaction_data)
     }
     fn handle(&self, args: Self::Args, _: &C) -> Self::Future {
         self.handle(args)
     }
-}
\ No newline at end of file
+}
+/// Handler of an entry in the context menu of messages
+pub trait MessageCommandHandler<Context> {
+    type Future;
+
+    fn name() -> &'static str;
+    /// Member permissions needed to use the command, also registered as its default permissions
+    fn required_permissions() -> Permissions {
+        Permissions::none()
+    }
+    /// Permissions the bot needs in the channel to carry out the command
+    fn required_bot_permissions() -> Permissions {
+        Permissions::none()
+    }
+    fn handle(&self, message: Message, context: &Context) -> Self::Future;
+}
+
+/// Handler of an entry in the context menu of users
+pub trait UserCommandHandler<Context> {
+    type Future;
+
+    fn name() -> &'static str;
+    /// Member permissions needed to use the command, also registered as its default permissions
+    fn required_permissions() -> Permissions {
+        Permissions::none()
+    }
+    /// Permissions the bot needs in the channel to carry out the command
+    fn required_bot_permissions() -> Permissions {
+        Permissions::none()
+    }
+    fn handle(&self, target: UserCommandTarget, context: &Context) -> Self::Future;
+}
+
+pub struct UserCommandTarget {
+    pub user: User,
+    /// Present when the command was invoked in a guild
+    #[allow(dead_code)]
+    pub member: Option<GuildMember>,
+}
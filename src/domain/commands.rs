@@ -1,54 +1,288 @@
 use crate::discord::rest::application_command::{
-    ApplicationCommand, ApplicationCommandOption,
+    ApplicationCommand, ApplicationCommandBuilder, ApplicationCommandOption, ApplicationCommandOptionBuilder,
+    ApplicationCommandOptionValue,
 };
 use crate::discord::rest::DiscordBotApiClient;
+use crate::discord::Snowflake;
+use crate::domain::bot::BotContext;
+use crate::domain::command_handlers::{CommandHandler, MessageCommandHandler, UserCommandHandler};
+use crate::domain::i18n::{Catalog, Translator};
+use crate::domain::note::Visibility;
+use crate::domain::interaction_handlers::{
+    DelCommandHandler, DiffCommandHandler, EchoCommandHandler, EditCommandHandler, GetCommandHandler,
+    HistoryCommandHandler, LsCommandHandler, NotesByUserCommandHandler, RenameCommandHandler, RestoreCommandHandler, SaveAsNoteCommandHandler,
+    SetCommandHandler,
+};
+
+pub async fn declare_commands(client: &DiscordBotApiClient, catalog: &Catalog) -> Result<(), Box<dyn std::error::Error>> {
+    for command in commands(client.app_id(), catalog) {
+        command.validate()?;
+        client.create_application_command(&command).await?;
+    }
+    Ok(())
+}
 
-pub async fn declare_commands(client: &DiscordBotApiClient) -> Result<(), Box<dyn std::error::Error>> {
-    let set = ApplicationCommand::build_for_application("set", client.app_id())
-        .with_description("Save new note")
+/// Commands of the bot, described in every language of the catalog.
+/// Messages are `command-<name>` for names and `command-<name>-description` for descriptions,
+/// options use `command-<name>-<option>` and `command-<name>-<option>-description`.
+fn commands(app_id: Snowflake, catalog: &Catalog) -> Vec<ApplicationCommand> {
+    let set = chat_input("set", app_id, catalog)
+        .with_default_member_permissions(<SetCommandHandler as CommandHandler<BotContext>>::required_permissions())
         .with_option(
-            ApplicationCommandOption::build_string_option("key")
-                .required()
-                .with_description("Key of note")
+            option(ApplicationCommandOption::build_string_option, "set", "key", catalog)
+                .not_required()
                 .finish(),
         )
         .with_option(
-            ApplicationCommandOption::build_string_option("value")
-                .required()
-                .with_description("Text of note")
+            option(ApplicationCommandOption::build_string_option, "set", "value", catalog)
+                .not_required()
+                .finish(),
+        )
+        .with_option(
+            option(ApplicationCommandOption::build_attachment_option, "set", "file", catalog)
+                .not_required()
+                .finish(),
+        )
+        .with_option(
+            option(ApplicationCommandOption::build_boolean_option, "set", "global", catalog)
+                .not_required()
+                .finish(),
+        )
+        .with_option(visibility_option("set", catalog))
+        .with_option(
+            option(ApplicationCommandOption::build_integer_option, "set", "ttl", catalog)
+                .with_min_value(0.0)
+                .not_required()
                 .finish(),
         )
         // TODO autocomplete
         .finish();
 
-    let get = ApplicationCommand::build_for_application("get", client.app_id())
-        .with_description("Read saved note")
+    let get = chat_input("get", app_id, catalog)
+        .with_default_member_permissions(<GetCommandHandler as CommandHandler<BotContext>>::required_permissions())
         .with_option(
-            ApplicationCommandOption::build_string_option("key")
+            option(ApplicationCommandOption::build_string_option, "get", "key", catalog)
                 .required()
-                .with_description("Key of note")
+                .finish(),
+        )
+        .with_option(
+            option(ApplicationCommandOption::build_boolean_option, "get", "as_file", catalog)
+                .not_required()
+                .finish(),
+        )
+        .with_option(
+            option(ApplicationCommandOption::build_boolean_option, "get", "global", catalog)
+                .not_required()
                 .finish(),
         )
         // TODO autocomplete
         .finish();
 
-    let echo = ApplicationCommand::build_for_application("echo", client.app_id())
-        .with_description("Reply with same text")
+    let echo = chat_input("echo", app_id, catalog)
+        .with_default_member_permissions(<EchoCommandHandler as CommandHandler<BotContext>>::required_permissions())
         .with_option(
-            ApplicationCommandOption::build_string_option("text")
+            option(ApplicationCommandOption::build_string_option, "echo", "text", catalog)
                 .required()
-                .with_description("Text to reply")
                 .finish(),
         )
         .finish();
 
-    let ls = ApplicationCommand::build_for_application("ls", client.app_id())
-        .with_description("List all available notes")
+    let ls = chat_input("ls", app_id, catalog)
+        .with_default_member_permissions(<LsCommandHandler as CommandHandler<BotContext>>::required_permissions())
+        .with_option(
+            option(ApplicationCommandOption::build_boolean_option, "ls", "global", catalog)
+                .not_required()
+                .finish(),
+        )
         .finish();
 
-    client.create_application_command(&set).await?;
-    client.create_application_command(&get).await?;
-    client.create_application_command(&echo).await?;
-    client.create_application_command(&ls).await?;
-    Ok(())
+    let edit = chat_input("edit", app_id, catalog)
+        .with_default_member_permissions(<EditCommandHandler as CommandHandler<BotContext>>::required_permissions())
+        .with_option(
+            option(ApplicationCommandOption::build_string_option, "edit", "key", catalog)
+                .required()
+                .finish(),
+        )
+        .with_option(
+            option(ApplicationCommandOption::build_boolean_option, "edit", "global", catalog)
+                .not_required()
+                .finish(),
+        )
+        .finish();
+
+    let rename = chat_input("rename", app_id, catalog)
+        .with_default_member_permissions(<RenameCommandHandler as CommandHandler<BotContext>>::required_permissions())
+        .with_option(
+            option(ApplicationCommandOption::build_string_option, "rename", "key", catalog)
+                .required()
+                .finish(),
+        )
+        .with_option(
+            option(ApplicationCommandOption::build_string_option, "rename", "new_key", catalog)
+                .required()
+                .finish(),
+        )
+        .with_option(
+            option(ApplicationCommandOption::build_boolean_option, "rename", "global", catalog)
+                .not_required()
+                .finish(),
+        )
+        .finish();
+
+    let del = chat_input("del", app_id, catalog)
+        .with_default_member_permissions(<DelCommandHandler as CommandHandler<BotContext>>::required_permissions())
+        .with_option(
+            option(ApplicationCommandOption::build_string_option, "del", "key", catalog)
+                .required()
+                .finish(),
+        )
+        .with_option(
+            option(ApplicationCommandOption::build_boolean_option, "del", "global", catalog)
+                .not_required()
+                .finish(),
+        )
+        .finish();
+
+    let history = chat_input("history", app_id, catalog)
+        .with_default_member_permissions(<HistoryCommandHandler as CommandHandler<BotContext>>::required_permissions())
+        .with_option(
+            option(ApplicationCommandOption::build_string_option, "history", "key", catalog)
+                .required()
+                .finish(),
+        )
+        .with_option(
+            option(ApplicationCommandOption::build_boolean_option, "history", "global", catalog)
+                .not_required()
+                .finish(),
+        )
+        .finish();
+
+    let diff = chat_input("diff", app_id, catalog)
+        .with_default_member_permissions(<DiffCommandHandler as CommandHandler<BotContext>>::required_permissions())
+        .with_option(
+            option(ApplicationCommandOption::build_string_option, "diff", "key", catalog)
+                .required()
+                .finish(),
+        )
+        .with_option(
+            option(ApplicationCommandOption::build_integer_option, "diff", "from", catalog)
+                .with_min_value(1.0)
+                .required()
+                .finish(),
+        )
+        .with_option(
+            option(ApplicationCommandOption::build_integer_option, "diff", "to", catalog)
+                .with_min_value(1.0)
+                .not_required()
+                .finish(),
+        )
+        .with_option(
+            option(ApplicationCommandOption::build_boolean_option, "diff", "global", catalog)
+                .not_required()
+                .finish(),
+        )
+        .finish();
+
+    let restore = chat_input("restore", app_id, catalog)
+        .with_default_member_permissions(<RestoreCommandHandler as CommandHandler<BotContext>>::required_permissions())
+        .with_option(
+            option(ApplicationCommandOption::build_string_option, "restore", "key", catalog)
+                .required()
+                .finish(),
+        )
+        .with_option(
+            option(ApplicationCommandOption::build_integer_option, "restore", "revision", catalog)
+                .with_min_value(1.0)
+                .required()
+                .finish(),
+        )
+        .with_option(
+            option(ApplicationCommandOption::build_boolean_option, "restore", "global", catalog)
+                .not_required()
+                .finish(),
+        )
+        .finish();
+
+    let save_as_note = ApplicationCommand::build_message_command("Save as note", app_id)
+        .with_name_localizations(catalog.localizations("command-save-as-note"))
+        .with_default_member_permissions(
+            <SaveAsNoteCommandHandler as MessageCommandHandler<BotContext>>::required_permissions(),
+        )
+        .finish();
+
+    let notes_by_user = ApplicationCommand::build_user_command("Notes by user", app_id)
+        .with_name_localizations(catalog.localizations("command-notes-by-user"))
+        .with_default_member_permissions(
+            <NotesByUserCommandHandler as UserCommandHandler<BotContext>>::required_permissions(),
+        )
+        .finish();
+
+    vec![set, get, echo, ls, edit, rename, del, history, diff, restore, save_as_note, notes_by_user]
+}
+
+/// Choice of note visibility, choices are named by `command-<name>-visibility-<code>`
+fn visibility_option(command: &str, catalog: &Catalog) -> ApplicationCommandOption {
+    let english = Translator::new(catalog.clone(), []);
+    Visibility::ALL
+        .iter()
+        .fold(
+            option(ApplicationCommandOption::build_string_option, command, "visibility", catalog).not_required(),
+            |option, visibility| {
+                let id = format!("command-{}-visibility-{}", command, visibility.code());
+                let value = ApplicationCommandOptionValue::Str(visibility.code().to_owned());
+                option.with_choice(&english.text(&id), value, catalog.localizations(&id))
+            },
+        )
+        .finish()
+}
+
+/// Slash command `name`, its default name is the one handlers are routed by
+fn chat_input<'b>(name: &'b str, app_id: Snowflake, catalog: &Catalog) -> ApplicationCommandBuilder<'b> {
+    let id = format!("command-{}", name);
+    let description = format!("{}-description", id);
+    ApplicationCommand::build_for_application(name, app_id)
+        .with_description(&Translator::new(catalog.clone(), []).text(&description))
+        .with_name_localizations(catalog.localizations(&id))
+        .with_description_localizations(catalog.localizations(&description))
+}
+
+fn option<'b>(
+    build: fn(&'b str) -> ApplicationCommandOptionBuilder<'b>,
+    command: &str,
+    name: &'b str,
+    catalog: &Catalog,
+) -> ApplicationCommandOptionBuilder<'b> {
+    let id = format!("command-{}-{}", command, name.replace('_', "-"));
+    let description = format!("{}-description", id);
+    build(name)
+        .with_description(&Translator::new(catalog.clone(), []).text(&description))
+        .with_name_localizations(catalog.localizations(&id))
+        .with_description_localizations(catalog.localizations(&description))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::commands;
+    use crate::discord::Snowflake;
+    use crate::domain::i18n::{Catalog, Translator};
+
+    #[test]
+    fn commands_are_valid_in_every_locale() {
+        let catalog = Catalog::bundled().unwrap();
+        let commands = commands(Snowflake::default(), &catalog);
+        let english = Translator::new(catalog.clone(), []);
+        for command in &commands {
+            command.validate().unwrap();
+            if command.description.is_empty() {
+                continue;
+            }
+            // the catalog's default name is what users see, it must route to the handler
+            assert_eq!(english.text(&format!("command-{}", command.name)), command.name);
+        }
+        let set = &commands[0];
+        assert_eq!(set.description, "Save new note. Opens an editor when value is omitted");
+        assert!(set.name_localizations.as_ref().unwrap().len() > 1);
+        let echo = commands.iter().find(|command| command.name == "echo").unwrap();
+        assert!(echo.default_member_permissions.is_none());
+    }
 }
@@ -0,0 +1,59 @@
+use crate::discord::interaction::modal::ModalSubmitValues;
+use crate::discord::interaction::InteractionData;
+use crate::domain::interaction_pipeline::{InteractionError, InteractionResponse};
+
+pub type ComponentHandlerResult = Result<InteractionResponse, InteractionError>;
+
+const CUSTOM_ID_SEPARATOR: char = ':';
+
+/// Handles interactions produced by message components (buttons and select menus).
+///
+/// Components are routed by the prefix of their `custom_id`: a component created with
+/// `custom_id("ls", "2")` has custom id `ls:2` and is handled by the handler
+/// whose `custom_id_prefix()` is `ls`, receiving `2` as `custom_id_args`.
+pub trait ComponentHandler<Context> {
+    type Args;
+    type Future;
+
+    fn custom_id_prefix() -> &'static str;
+    fn parse_args(custom_id_args: &str, interaction_data: &InteractionData) -> Option<Self::Args>;
+    fn handle(&self, args: Self::Args, context: &Context) -> Self::Future;
+}
+
+/// Handles submitted modals. Modals are routed by `custom_id` prefix the same way as components.
+pub trait ModalSubmitHandler<Context> {
+    type Args;
+    type Future;
+
+    fn custom_id_prefix() -> &'static str;
+    fn parse_args(custom_id_args: &str, values: &ModalSubmitValues) -> Option<Self::Args>;
+    fn handle(&self, args: Self::Args, context: &Context) -> Self::Future;
+}
+
+pub fn custom_id(prefix: &str, args: &str) -> String {
+    format!("{prefix}{CUSTOM_ID_SEPARATOR}{args}")
+}
+
+/// Splits custom id into the routing prefix and handler specific arguments
+pub fn split_custom_id(custom_id: &str) -> (&str, &str) {
+    custom_id
+        .split_once(CUSTOM_ID_SEPARATOR)
+        .unwrap_or((custom_id, ""))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{custom_id, split_custom_id};
+
+    #[test]
+    fn custom_id_roundtrip() {
+        let id = custom_id("ls", "page:2");
+        assert_eq!(id, "ls:page:2");
+        assert_eq!(split_custom_id(&id), ("ls", "page:2"));
+    }
+
+    #[test]
+    fn custom_id_without_args() {
+        assert_eq!(split_custom_id("confirm"), ("confirm", ""));
+    }
+}
@@ -0,0 +1,355 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use chrono::Utc;
+use log::{error, warn};
+use serde::{Deserialize, Serialize};
+
+use crate::discord::interaction::{Interaction, InteractionCallback, InteractionCallbackMessage};
+use crate::discord::Snowflake;
+use crate::domain::bot::Get;
+use crate::domain::i18n::{Arg, Translator};
+use crate::domain::interaction_pipeline::{Middleware, Route};
+use crate::domain::store::Storage;
+
+/// Entries are swept after this many checks, so idle users do not pile up in memory
+const SWEEP_INTERVAL: u64 = 1000;
+
+/// Who shares a limit
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum CooldownScope {
+    User,
+    Channel,
+    /// Interactions outside of guilds are limited per user
+    Guild,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum RateLimit {
+    /// At most `max_uses` within any period of `window`
+    SlidingWindow { max_uses: u32, window: Duration },
+    /// Bursts of up to `capacity` uses, one use is regained every `refill`
+    TokenBucket { capacity: u32, refill: Duration },
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Cooldown {
+    pub scope: CooldownScope,
+    pub limit: RateLimit,
+}
+
+impl Cooldown {
+    pub fn per_user(limit: RateLimit) -> Self {
+        Cooldown { scope: CooldownScope::User, limit }
+    }
+
+    pub fn per_channel(limit: RateLimit) -> Self {
+        Cooldown { scope: CooldownScope::Channel, limit }
+    }
+
+    pub fn per_guild(limit: RateLimit) -> Self {
+        Cooldown { scope: CooldownScope::Guild, limit }
+    }
+
+    fn subject(&self, interaction: &Interaction) -> Option<Snowflake> {
+        let user = || interaction.invoking_user().map(|user| user.id);
+        match self.scope {
+            CooldownScope::User => user(),
+            CooldownScope::Channel => interaction.channel_id,
+            CooldownScope::Guild => interaction.guild_id.or_else(user),
+        }
+    }
+}
+
+/// Usage of a limit by one subject. Times are milliseconds since the Unix epoch,
+/// so the state stays meaningful after a restart.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+enum Usage {
+    SlidingWindow { uses: Vec<i64> },
+    TokenBucket { tokens: f64, updated: i64 },
+}
+
+impl Usage {
+    fn new(limit: &RateLimit) -> Self {
+        match limit {
+            RateLimit::SlidingWindow { .. } => Usage::SlidingWindow { uses: vec![] },
+            RateLimit::TokenBucket { capacity, .. } => Usage::TokenBucket {
+                tokens: f64::from(*capacity),
+                updated: 0,
+            },
+        }
+    }
+
+    /// Takes one use, or returns the time until one is available
+    fn take(&mut self, limit: &RateLimit, now: i64) -> Result<(), Duration> {
+        match (self, limit) {
+            (Usage::SlidingWindow { uses }, RateLimit::SlidingWindow { max_uses, window }) => {
+                let window = window.as_millis() as i64;
+                uses.retain(|used| now - used < window);
+                if uses.len() < *max_uses as usize {
+                    uses.push(now);
+                    Ok(())
+                } else {
+                    let oldest = uses.iter().min().copied().unwrap_or(now);
+                    Err(Duration::from_millis((oldest + window - now).max(0) as u64))
+                }
+            }
+            (Usage::TokenBucket { tokens, updated }, RateLimit::TokenBucket { capacity, refill }) => {
+                let refill = refill.as_millis().max(1) as f64;
+                let regained = (now - *updated).max(0) as f64 / refill;
+                *tokens = (*tokens + regained).min(f64::from(*capacity));
+                *updated = now;
+                if *tokens >= 1.0 {
+                    *tokens -= 1.0;
+                    Ok(())
+                } else {
+                    Err(Duration::from_millis(((1.0 - *tokens) * refill).ceil() as u64))
+                }
+            }
+            // limit was reconfigured since the state was persisted
+            (usage, limit) => {
+                *usage = Usage::new(limit);
+                usage.take(limit, now)
+            }
+        }
+    }
+
+    /// Idle state is the same as no state at all
+    fn is_idle(&self, limit: &RateLimit, now: i64) -> bool {
+        match (self, limit) {
+            (Usage::SlidingWindow { uses }, RateLimit::SlidingWindow { window, .. }) => {
+                uses.iter().all(|used| now - used >= window.as_millis() as i64)
+            }
+            (Usage::TokenBucket { tokens, updated }, RateLimit::TokenBucket { capacity, refill }) => {
+                let regained = (now - updated).max(0) as f64 / refill.as_millis().max(1) as f64;
+                tokens + regained >= f64::from(*capacity)
+            }
+            _ => true,
+        }
+    }
+}
+
+#[derive(Default)]
+struct State {
+    usage: HashMap<String, Usage>,
+    checks: u64,
+}
+
+/// Rate limits of commands, applied as a pipeline middleware.
+///
+/// State lives in memory and is shared by clones, so one instance can serve all workers.
+/// With persistence every change is also written to storage and restored on startup.
+#[derive(Clone, Default)]
+pub struct Cooldowns {
+    limits: Arc<HashMap<Route, Cooldown>>,
+    state: Arc<Mutex<State>>,
+    storage: Option<Storage>,
+}
+
+impl Cooldowns {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_cooldown(mut self, route: Route, cooldown: Cooldown) -> Self {
+        Arc::make_mut(&mut self.limits).insert(route, cooldown);
+        self
+    }
+
+    pub fn with_persistence(mut self, storage: Storage) -> Self {
+        self.storage = Some(storage);
+        self
+    }
+
+    /// Loads persisted state, skipping entries which can not be read
+    pub async fn restore(&self) {
+        let storage = match &self.storage {
+            Some(storage) => storage,
+            None => return,
+        };
+        let keys = match storage.list().await {
+            Ok(keys) => keys,
+            Err(e) => {
+                error!("Failed to restore cooldowns: {}", e);
+                return;
+            }
+        };
+        for key in keys {
+            let usage = storage
+                .read(&key)
+                .await
+                .ok()
+                .and_then(|value| serde_json::from_str::<Usage>(&value).ok());
+            match usage {
+                Some(usage) => {
+                    self.state.lock().unwrap().usage.insert(key, usage);
+                }
+                None => warn!("Skipping unreadable cooldown state {}", key),
+            }
+        }
+    }
+
+    /// Records a use of the interaction's route, or returns how long to wait for the next one
+    fn check(&self, interaction: &Interaction, now: i64) -> Result<(), Duration> {
+        let route = match Route::of(interaction) {
+            Some(route) => route,
+            None => return Ok(()),
+        };
+        let cooldown = match self.limits.get(&route) {
+            Some(cooldown) => cooldown,
+            None => return Ok(()),
+        };
+        let subject = match cooldown.subject(interaction) {
+            Some(subject) => subject,
+            None => return Ok(()),
+        };
+        let key = format!("{}/{}", route, subject);
+
+        let mut state = self.state.lock().unwrap();
+        state.checks += 1;
+        if state.checks.is_multiple_of(SWEEP_INTERVAL) {
+            self.sweep(&mut state, now);
+        }
+        let usage = state
+            .usage
+            .entry(key.clone())
+            .or_insert_with(|| Usage::new(&cooldown.limit));
+        let result = usage.take(&cooldown.limit, now);
+        if result.is_ok() {
+            self.persist(key, usage.clone());
+        }
+        result
+    }
+
+    fn sweep(&self, state: &mut State, now: i64) {
+        let limits = &self.limits;
+        let mut removed = vec![];
+        state.usage.retain(|key, usage| {
+            let keep = limits
+                .iter()
+                .find(|(route, _)| key.starts_with(&format!("{}/", route)))
+                .map(|(_, cooldown)| !usage.is_idle(&cooldown.limit, now))
+                .unwrap_or(false);
+            if !keep {
+                removed.push(key.clone());
+            }
+            keep
+        });
+        if let Some(storage) = self.storage.clone() {
+            actix_rt::spawn(async move {
+                for key in removed {
+                    let _ = storage.delete(&key).await;
+                }
+            });
+        }
+    }
+
+    fn persist(&self, key: String, usage: Usage) {
+        if let Some(storage) = self.storage.clone() {
+            actix_rt::spawn(async move {
+                let value = serde_json::to_string(&usage).expect("cooldown state");
+                if let Err(e) = storage.upsert(&key, &value).await {
+                    error!("Failed to persist cooldown {}: {}", key, e);
+                }
+            });
+        }
+    }
+}
+
+impl<C: Get<Translator>> Middleware<C> for Cooldowns {
+    fn before(&self, interaction: &Interaction, context: &C) -> Option<InteractionCallback> {
+        let retry_after = self.check(interaction, Utc::now().timestamp_millis()).err()?;
+        let message = InteractionCallbackMessage {
+            content: Some(try_again_message(context.get(), retry_after)),
+            ..Default::default()
+        };
+        Some(InteractionCallback::channel_message_with_source(message.ephemeral()))
+    }
+}
+
+fn try_again_message(translator: &Translator, retry_after: Duration) -> String {
+    let seconds = retry_after.as_secs() + u64::from(retry_after.subsec_nanos() > 0);
+    translator.message("try-again", &[("seconds", Arg::from(seconds))])
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use serde_json::json;
+
+    use super::{Cooldown, Cooldowns, RateLimit};
+    use crate::discord::interaction::Interaction;
+    use crate::domain::i18n::{Catalog, Translator};
+    use crate::domain::interaction_pipeline::Route;
+
+    fn set_command(user_id: &str, channel_id: &str) -> Interaction {
+        serde_json::from_value(json!({
+            "application_id": "1",
+            "id": "2",
+            "token": "token",
+            "type": 2,
+            "channel_id": channel_id,
+            "user": {"id": user_id, "username": "user"},
+            "data": {"id": "3", "name": "set", "type": 1},
+            "locale": "de",
+            "version": 1
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn sliding_window_limits_per_user() {
+        let cooldowns = Cooldowns::new().with_cooldown(
+            Route::command("set"),
+            Cooldown::per_user(RateLimit::SlidingWindow { max_uses: 2, window: Duration::from_secs(10) }),
+        );
+        let alice = set_command("10", "20");
+        let bob = set_command("11", "20");
+
+        assert!(cooldowns.check(&alice, 0).is_ok());
+        assert!(cooldowns.check(&alice, 1_000).is_ok());
+        assert_eq!(cooldowns.check(&alice, 4_000), Err(Duration::from_secs(6)));
+        assert!(cooldowns.check(&bob, 4_000).is_ok());
+        assert!(cooldowns.check(&alice, 10_000).is_ok());
+    }
+
+    #[test]
+    fn token_bucket_refills() {
+        let cooldowns = Cooldowns::new().with_cooldown(
+            Route::command("set"),
+            Cooldown::per_channel(RateLimit::TokenBucket { capacity: 2, refill: Duration::from_secs(5) }),
+        );
+        let first = set_command("10", "20");
+        let second = set_command("11", "20");
+
+        assert!(cooldowns.check(&first, 0).is_ok());
+        assert!(cooldowns.check(&second, 0).is_ok());
+        assert_eq!(cooldowns.check(&first, 1_000), Err(Duration::from_secs(4)));
+        assert!(cooldowns.check(&first, 5_000).is_ok());
+    }
+
+    #[test]
+    fn other_commands_are_not_limited() {
+        let cooldowns = Cooldowns::new().with_cooldown(
+            Route::command("get"),
+            Cooldown::per_user(RateLimit::SlidingWindow { max_uses: 0, window: Duration::from_secs(10) }),
+        );
+        assert!(cooldowns.check(&set_command("10", "20"), 0).is_ok());
+    }
+
+    #[test]
+    fn localized_reply() {
+        let german = Catalog::bundled().unwrap().translator(&set_command("10", "20"));
+        assert_eq!(
+            super::try_again_message(&german, Duration::from_millis(2500)),
+            "Zu viele Anfragen, versuche es in 3 Sekunden erneut"
+        );
+        assert_eq!(
+            super::try_again_message(&Translator::default(), Duration::from_secs(1)),
+            "Too many requests, try again in 1 second"
+        );
+    }
+}
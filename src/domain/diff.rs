@@ -0,0 +1,58 @@
+/// Largest `lines(old) * lines(new)` compared line by line, bigger texts are replaced as a whole
+const MAX_TABLE_SIZE: usize = 1_000_000;
+
+/// Line diff of `old` and `new` in unified diff style: unchanged lines start with two spaces,
+/// removed ones with `- `, added ones with `+ `
+pub fn line_diff(old: &str, new: &str) -> String {
+    let old: Vec<&str> = old.lines().collect();
+    let new: Vec<&str> = new.lines().collect();
+    if old.len().saturating_mul(new.len()) > MAX_TABLE_SIZE {
+        let removed = old.iter().map(|line| format!("- {}", line));
+        let added = new.iter().map(|line| format!("+ {}", line));
+        return removed.chain(added).collect::<Vec<_>>().join("\n");
+    }
+
+    // longest common subsequence of the suffixes starting at each pair of lines
+    let mut common = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            common[i][j] = if old[i] == new[j] {
+                common[i + 1][j + 1] + 1
+            } else {
+                common[i + 1][j].max(common[i][j + 1])
+            };
+        }
+    }
+
+    let mut lines = vec![];
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i] == new[j] {
+            lines.push(format!("  {}", old[i]));
+            i += 1;
+            j += 1;
+        } else if j == new.len() || (i < old.len() && common[i + 1][j] >= common[i][j + 1]) {
+            lines.push(format!("- {}", old[i]));
+            i += 1;
+        } else {
+            lines.push(format!("+ {}", new[j]));
+            j += 1;
+        }
+    }
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::line_diff;
+
+    #[test]
+    fn changed_lines_are_marked() {
+        assert_eq!(
+            line_diff("milk\neggs\nbread", "milk\nbutter\nbread\njam"),
+            "  milk\n- eggs\n+ butter\n  bread\n+ jam"
+        );
+        assert_eq!(line_diff("same", "same"), "  same");
+        assert_eq!(line_diff("", "new"), "+ new");
+    }
+}
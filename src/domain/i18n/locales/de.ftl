@@ -0,0 +1,136 @@
+## Notes
+note-saved = Wert der Notiz erfolgreich gespeichert!
+note-renamed = Notiz umbenannt
+note-restored = Version { $revision } wiederhergestellt
+history-title = Verlauf von „{ $key }“
+history-revision = **#{ $revision }** von { $editor }, { $time }
+history-unknown-editor = unbekannt
+diff-title = „{ $key }“: Version { $from } → { $to }
+note-deleted = Notiz „{ $key }“ gelöscht
+del-confirm = Notiz „{ $key }“ löschen? Das kann nicht rückgängig gemacht werden.
+del-confirm-button = Löschen
+del-cancel-button = Abbrechen
+del-cancelled = Es wurde nichts gelöscht
+notes-title = Notizen
+notes-empty = Es gibt noch keine Notizen
+notes-total = Gesamt: { $total }
+notes-by-user = Notizen von { $user }
+set-modal-title = Notiz speichern
+set-modal-key = Schlüssel
+set-modal-key-placeholder = Schlüssel der Notiz
+set-modal-value = Wert
+set-modal-value-placeholder = Text der Notiz
+
+## Pagination
+page-label = Seite { $page }/{ $pages }
+page-previous = ◀ Zurück
+page-next = Weiter ▶
+
+## Guards
+try-again = Zu viele Anfragen, versuche es in { $seconds ->
+    [one] { $seconds } Sekunde
+   *[other] { $seconds } Sekunden
+} erneut
+missing-member-permissions = Dir fehlen Berechtigungen: { $permissions }
+missing-bot-permissions = Mir fehlen Berechtigungen: { $permissions }
+
+## Errors
+error-not-implemented = Dieser Befehl ist nicht implementiert
+error-unknown-command = Dieser Befehl ist unbekannt
+error-invalid-command = Ungültige Befehlsparameter
+error-note-not-found = Notiz „{ $key }“ nicht gefunden
+error-note-not-found-unnamed = Notiz nicht gefunden
+error-note-exists = Notiz „{ $key }“ existiert bereits
+error-note-exists-unnamed = Notiz existiert bereits
+error-unsupported-attachment = Nur UTF-8-Textdateien bis { $max-kib } KiB werden unterstützt
+error-access-denied = Kein Zugriff auf Notiz „{ $key }“
+error-access-denied-unnamed = Kein Zugriff auf diese Notiz
+error-global-scope-denied = Globale Notizen sind den Bot-Admins vorbehalten
+error-not-invoker = Nur wer diesen Befehl ausgeführt hat, kann diese Schaltflächen benutzen
+error-invalid-key = Notizschlüssel müssen 1 bis { $max-length } Zeichen lang sein
+error-too-long-to-edit = Notizen mit mehr als { $max-length } Zeichen können nicht bearbeitet werden, ersetze sie stattdessen mit /set
+error-key-changed = Der Schlüssel kann beim Bearbeiten nicht geändert werden, benenne die Notiz stattdessen mit /rename um
+error-revision-not-found = Version { $revision } dieser Notiz ist nicht mehr vorhanden
+error-unexpected = Etwas ist bei uns schiefgelaufen
+error-reported = Etwas ist bei uns schiefgelaufen. Falls das öfter passiert, melde die Fehler-ID `{ $id }`
+
+## Commands
+command-set = speichern
+command-set-description = Neue Notiz speichern. Öffnet einen Editor, wenn der Wert fehlt
+command-set-key = schlüssel
+command-set-key-description = Schlüssel der Notiz
+command-set-value = wert
+command-set-value-description = Text der Notiz
+command-set-file = datei
+command-set-file-description = Textdatei, die statt des Werts gespeichert wird
+command-set-global = global
+command-set-global-description = In den globalen Notizen der Bot-Admins speichern
+command-set-visibility = sichtbarkeit
+command-set-visibility-description = Wer die Notiz außer dir lesen darf
+command-set-visibility-private = Nur ich
+command-set-visibility-guild = Mitglieder dieses Servers
+command-set-visibility-public = Alle
+command-set-ttl = ttl
+command-set-ttl-description = Minuten bis die Notiz abläuft, 0 läuft nie ab
+command-get = lesen
+command-get-description = Gespeicherte Notiz lesen
+command-get-key = schlüssel
+command-get-key-description = Schlüssel der Notiz
+command-get-as-file = als_datei
+command-get-as-file-description = Notiz als Textdatei senden
+command-get-global = global
+command-get-global-description = Aus den globalen Notizen der Bot-Admins lesen
+command-echo = echo
+command-echo-description = Mit demselben Text antworten
+command-echo-text = text
+command-echo-text-description = Text der Antwort
+command-ls = ls
+command-ls-description = Alle verfügbaren Notizen auflisten
+command-ls-global = global
+command-ls-global-description = Globale Notizen der Bot-Admins auflisten
+command-edit = bearbeiten
+command-edit-description = Notiz in einem mit ihrem Wert gefüllten Editor bearbeiten
+command-edit-key = schlüssel
+command-edit-key-description = Schlüssel der Notiz
+command-edit-global = global
+command-edit-global-description = Globale Notiz der Bot-Admins bearbeiten
+command-rename = umbenennen
+command-rename-description = Notiz einen neuen Schlüssel geben
+command-rename-key = schlüssel
+command-rename-key-description = Aktueller Schlüssel der Notiz
+command-rename-new-key = neuer_schlüssel
+command-rename-new-key-description = Neuer Schlüssel der Notiz
+command-rename-global = global
+command-rename-global-description = Globale Notiz der Bot-Admins umbenennen
+command-del = löschen
+command-del-description = Notiz nach Bestätigung löschen
+command-del-key = schlüssel
+command-del-key-description = Schlüssel der Notiz
+command-del-global = global
+command-del-global-description = Globale Notiz der Bot-Admins löschen
+command-history = verlauf
+command-history-description = Frühere Versionen einer Notiz anzeigen
+command-history-key = schlüssel
+command-history-key-description = Schlüssel der Notiz
+command-history-global = global
+command-history-global-description = Globale Notiz der Bot-Admins anzeigen
+command-diff = vergleichen
+command-diff-description = Änderungen zwischen zwei Versionen einer Notiz anzeigen
+command-diff-key = schlüssel
+command-diff-key-description = Schlüssel der Notiz
+command-diff-from = von
+command-diff-from-description = Ältere Version
+command-diff-to = bis
+command-diff-to-description = Neuere Version, standardmäßig die aktuelle
+command-diff-global = global
+command-diff-global-description = Globale Notiz der Bot-Admins vergleichen
+command-restore = wiederherstellen
+command-restore-description = Frühere Version zum aktuellen Wert einer Notiz machen
+command-restore-key = schlüssel
+command-restore-key-description = Schlüssel der Notiz
+command-restore-revision = version
+command-restore-revision-description = Wiederherzustellende Version, siehe /verlauf
+command-restore-global = global
+command-restore-global-description = Globale Notiz der Bot-Admins wiederherstellen
+command-save-as-note = Als Notiz speichern
+command-notes-by-user = Notizen des Nutzers
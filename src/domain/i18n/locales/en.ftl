@@ -0,0 +1,140 @@
+# Messages of the bot, the default language.
+# Other languages fall back to these when they miss a message.
+
+## Notes
+note-saved = Successfully set value for note!
+note-renamed = Note renamed
+note-restored = Restored revision { $revision }
+history-title = History of "{ $key }"
+history-revision = **#{ $revision }** by { $editor }, { $time }
+history-unknown-editor = unknown author
+diff-title = "{ $key }": revision { $from } → { $to }
+note-deleted = Note "{ $key }" deleted
+del-confirm = Delete note "{ $key }"? This cannot be undone.
+del-confirm-button = Delete
+del-cancel-button = Cancel
+del-cancelled = Nothing was deleted
+notes-title = Notes
+notes-empty = There are no notes yet
+notes-total = Total: { $total }
+notes-by-user = Notes by { $user }
+set-modal-title = Save note
+set-modal-key = Key
+set-modal-key-placeholder = Key of note
+set-modal-value = Value
+set-modal-value-placeholder = Text of note
+
+## Pagination
+page-label = Page { $page }/{ $pages }
+page-previous = ◀ Previous
+page-next = Next ▶
+
+## Guards
+try-again = Too many requests, try again in { $seconds ->
+    [one] { $seconds } second
+   *[other] { $seconds } seconds
+}
+missing-member-permissions = You are missing permissions: { $permissions }
+missing-bot-permissions = I am missing permissions: { $permissions }
+
+## Errors
+error-not-implemented = This command is not implemented
+error-unknown-command = This command is unknown
+error-invalid-command = Invalid command parameters
+error-note-not-found = Note "{ $key }" not found
+error-note-not-found-unnamed = Note not found
+error-note-exists = Note "{ $key }" already exists
+error-note-exists-unnamed = Note already exists
+error-unsupported-attachment = Only UTF-8 text files up to { $max-kib } KiB are supported
+error-access-denied = You may not access note "{ $key }"
+error-access-denied-unnamed = You may not access this note
+error-global-scope-denied = Global notes are reserved to bot admins
+error-not-invoker = Only the user who ran this command can use these buttons
+error-invalid-key = Note keys must be 1 to { $max-length } characters long
+error-too-long-to-edit = Notes longer than { $max-length } characters can't be edited, replace them with /set instead
+error-key-changed = The key can't be changed while editing a note, rename it with /rename instead
+error-revision-not-found = Revision { $revision } of this note is not kept
+error-unexpected = Something went wrong on our side
+error-reported = Something went wrong on our side. If it keeps happening, report error id `{ $id }`
+
+## Commands
+# Default names must match the names handlers are routed by
+command-set = set
+command-set-description = Save new note. Opens an editor when value is omitted
+command-set-key = key
+command-set-key-description = Key of note
+command-set-value = value
+command-set-value-description = Text of note
+command-set-file = file
+command-set-file-description = Text file to save instead of value
+command-set-global = global
+command-set-global-description = Save to the global notes of bot admins
+command-set-visibility = visibility
+command-set-visibility-description = Who may read the note besides you
+command-set-visibility-private = Only me
+command-set-visibility-guild = Members of this server
+command-set-visibility-public = Everyone
+command-set-ttl = ttl
+command-set-ttl-description = Minutes until the note expires, 0 never expires
+command-get = get
+command-get-description = Read saved note
+command-get-key = key
+command-get-key-description = Key of note
+command-get-as-file = as_file
+command-get-as-file-description = Send note as a text file
+command-get-global = global
+command-get-global-description = Read from the global notes of bot admins
+command-echo = echo
+command-echo-description = Reply with same text
+command-echo-text = text
+command-echo-text-description = Text to reply
+command-ls = ls
+command-ls-description = List all available notes
+command-ls-global = global
+command-ls-global-description = List the global notes of bot admins
+command-edit = edit
+command-edit-description = Edit a note in an editor prefilled with its value
+command-edit-key = key
+command-edit-key-description = Key of note
+command-edit-global = global
+command-edit-global-description = Edit a global note of bot admins
+command-rename = rename
+command-rename-description = Give a note a new key
+command-rename-key = key
+command-rename-key-description = Current key of note
+command-rename-new-key = new_key
+command-rename-new-key-description = New key of note
+command-rename-global = global
+command-rename-global-description = Rename a global note of bot admins
+command-del = del
+command-del-description = Delete a note after confirmation
+command-del-key = key
+command-del-key-description = Key of note
+command-del-global = global
+command-del-global-description = Delete a global note of bot admins
+command-history = history
+command-history-description = Show earlier revisions of a note
+command-history-key = key
+command-history-key-description = Key of note
+command-history-global = global
+command-history-global-description = Show a global note of bot admins
+command-diff = diff
+command-diff-description = Show changes between two revisions of a note
+command-diff-key = key
+command-diff-key-description = Key of note
+command-diff-from = from
+command-diff-from-description = Older revision
+command-diff-to = to
+command-diff-to-description = Newer revision, the current one by default
+command-diff-global = global
+command-diff-global-description = Compare a global note of bot admins
+command-restore = restore
+command-restore-description = Make an earlier revision the current value of a note
+command-restore-key = key
+command-restore-key-description = Key of note
+command-restore-revision = revision
+command-restore-revision-description = Revision to restore, see /history
+command-restore-global = global
+command-restore-global-description = Restore a global note of bot admins
+command-save-as-note = Save as note
+command-notes-by-user = Notes by user
@@ -0,0 +1,136 @@
+## Notes
+note-saved = ¡Valor de la nota guardado!
+note-renamed = Nota renombrada
+note-restored = Versión { $revision } restaurada
+history-title = Historial de "{ $key }"
+history-revision = **#{ $revision }** de { $editor }, { $time }
+history-unknown-editor = autor desconocido
+diff-title = "{ $key }": versión { $from } → { $to }
+note-deleted = Nota "{ $key }" eliminada
+del-confirm = ¿Eliminar la nota "{ $key }"? No se puede deshacer.
+del-confirm-button = Eliminar
+del-cancel-button = Cancelar
+del-cancelled = No se eliminó nada
+notes-title = Notas
+notes-empty = Todavía no hay notas
+notes-total = Total: { $total }
+notes-by-user = Notas de { $user }
+set-modal-title = Guardar nota
+set-modal-key = Clave
+set-modal-key-placeholder = Clave de la nota
+set-modal-value = Valor
+set-modal-value-placeholder = Texto de la nota
+
+## Pagination
+page-label = Página { $page }/{ $pages }
+page-previous = ◀ Anterior
+page-next = Siguiente ▶
+
+## Guards
+try-again = Demasiadas solicitudes, inténtalo de nuevo en { $seconds ->
+    [one] { $seconds } segundo
+   *[other] { $seconds } segundos
+}
+missing-member-permissions = Te faltan permisos: { $permissions }
+missing-bot-permissions = Me faltan permisos: { $permissions }
+
+## Errors
+error-not-implemented = Este comando no está implementado
+error-unknown-command = Este comando es desconocido
+error-invalid-command = Parámetros de comando no válidos
+error-note-not-found = No se encontró la nota «{ $key }»
+error-note-not-found-unnamed = No se encontró la nota
+error-note-exists = La nota «{ $key }» ya existe
+error-note-exists-unnamed = La nota ya existe
+error-unsupported-attachment = Solo se admiten archivos de texto UTF-8 de hasta { $max-kib } KiB
+error-access-denied = No tienes acceso a la nota "{ $key }"
+error-access-denied-unnamed = No tienes acceso a esta nota
+error-global-scope-denied = Las notas globales están reservadas a los administradores del bot
+error-not-invoker = Solo quien ejecutó este comando puede usar estos botones
+error-invalid-key = Las claves de las notas deben tener entre 1 y { $max-length } caracteres
+error-too-long-to-edit = Las notas de más de { $max-length } caracteres no se pueden editar, reemplázalas con /set
+error-key-changed = La clave no se puede cambiar al editar una nota, renómbrala con /rename
+error-revision-not-found = La versión { $revision } de esta nota ya no se conserva
+error-unexpected = Algo salió mal de nuestro lado
+error-reported = Algo salió mal de nuestro lado. Si sigue ocurriendo, informa el id de error `{ $id }`
+
+## Commands
+command-set = guardar
+command-set-description = Guardar una nota nueva. Abre un editor si se omite el valor
+command-set-key = clave
+command-set-key-description = Clave de la nota
+command-set-value = valor
+command-set-value-description = Texto de la nota
+command-set-file = archivo
+command-set-file-description = Archivo de texto a guardar en lugar del valor
+command-set-global = global
+command-set-global-description = Guardar en las notas globales de los administradores del bot
+command-set-visibility = visibilidad
+command-set-visibility-description = Quién más puede leer la nota
+command-set-visibility-private = Solo yo
+command-set-visibility-guild = Miembros de este servidor
+command-set-visibility-public = Todos
+command-set-ttl = ttl
+command-set-ttl-description = Minutos hasta que la nota caduque, 0 nunca caduca
+command-get = leer
+command-get-description = Leer una nota guardada
+command-get-key = clave
+command-get-key-description = Clave de la nota
+command-get-as-file = como_archivo
+command-get-as-file-description = Enviar la nota como archivo de texto
+command-get-global = global
+command-get-global-description = Leer de las notas globales de los administradores del bot
+command-echo = eco
+command-echo-description = Responder con el mismo texto
+command-echo-text = texto
+command-echo-text-description = Texto de la respuesta
+command-ls = ls
+command-ls-description = Listar todas las notas disponibles
+command-ls-global = global
+command-ls-global-description = Listar las notas globales de los administradores del bot
+command-edit = editar
+command-edit-description = Editar una nota en un editor con su valor
+command-edit-key = clave
+command-edit-key-description = Clave de la nota
+command-edit-global = global
+command-edit-global-description = Editar una nota global de los administradores del bot
+command-rename = renombrar
+command-rename-description = Dar una nueva clave a una nota
+command-rename-key = clave
+command-rename-key-description = Clave actual de la nota
+command-rename-new-key = nueva_clave
+command-rename-new-key-description = Nueva clave de la nota
+command-rename-global = global
+command-rename-global-description = Renombrar una nota global de los administradores del bot
+command-del = borrar
+command-del-description = Eliminar una nota tras confirmar
+command-del-key = clave
+command-del-key-description = Clave de la nota
+command-del-global = global
+command-del-global-description = Eliminar una nota global de los administradores del bot
+command-history = historial
+command-history-description = Mostrar versiones anteriores de una nota
+command-history-key = clave
+command-history-key-description = Clave de la nota
+command-history-global = global
+command-history-global-description = Mostrar una nota global de los administradores del bot
+command-diff = comparar
+command-diff-description = Mostrar cambios entre dos versiones de una nota
+command-diff-key = clave
+command-diff-key-description = Clave de la nota
+command-diff-from = desde
+command-diff-from-description = Versión anterior
+command-diff-to = hasta
+command-diff-to-description = Versión posterior, la actual por defecto
+command-diff-global = global
+command-diff-global-description = Comparar una nota global de los administradores del bot
+command-restore = restaurar
+command-restore-description = Convertir una versión anterior en el valor actual de una nota
+command-restore-key = clave
+command-restore-key-description = Clave de la nota
+command-restore-revision = version
+command-restore-revision-description = Versión a restaurar, ver /historial
+command-restore-global = global
+command-restore-global-description = Restaurar una nota global de los administradores del bot
+command-save-as-note = Guardar como nota
+command-notes-by-user = Notas del usuario
@@ -0,0 +1,136 @@
+## Notes
+note-saved = Valeur de la note enregistrée !
+note-renamed = Note renommée
+note-restored = Version { $revision } restaurée
+history-title = Historique de « { $key } »
+history-revision = **#{ $revision }** par { $editor }, { $time }
+history-unknown-editor = auteur inconnu
+diff-title = « { $key } » : version { $from } → { $to }
+note-deleted = Note « { $key } » supprimée
+del-confirm = Supprimer la note « { $key } » ? Cette action est irréversible.
+del-confirm-button = Supprimer
+del-cancel-button = Annuler
+del-cancelled = Rien n’a été supprimé
+notes-title = Notes
+notes-empty = Il n'y a pas encore de notes
+notes-total = Total : { $total }
+notes-by-user = Notes de { $user }
+set-modal-title = Enregistrer la note
+set-modal-key = Clé
+set-modal-key-placeholder = Clé de la note
+set-modal-value = Valeur
+set-modal-value-placeholder = Texte de la note
+
+## Pagination
+page-label = Page { $page }/{ $pages }
+page-previous = ◀ Précédent
+page-next = Suivant ▶
+
+## Guards
+try-again = Trop de requêtes, réessayez dans { $seconds ->
+    [one] { $seconds } seconde
+   *[other] { $seconds } secondes
+}
+missing-member-permissions = Il vous manque des permissions : { $permissions }
+missing-bot-permissions = Il me manque des permissions : { $permissions }
+
+## Errors
+error-not-implemented = Cette commande n'est pas implémentée
+error-unknown-command = Cette commande est inconnue
+error-invalid-command = Paramètres de commande invalides
+error-note-not-found = Note « { $key } » introuvable
+error-note-not-found-unnamed = Note introuvable
+error-note-exists = La note « { $key } » existe déjà
+error-note-exists-unnamed = La note existe déjà
+error-unsupported-attachment = Seuls les fichiers texte UTF-8 jusqu'à { $max-kib } Kio sont pris en charge
+error-access-denied = Vous n’avez pas accès à la note « { $key } »
+error-access-denied-unnamed = Vous n’avez pas accès à cette note
+error-global-scope-denied = Les notes globales sont réservées aux administrateurs du bot
+error-not-invoker = Seule la personne qui a lancé cette commande peut utiliser ces boutons
+error-invalid-key = Les clés des notes doivent faire entre 1 et { $max-length } caractères
+error-too-long-to-edit = Les notes de plus de { $max-length } caractères ne peuvent pas être modifiées, remplacez-les avec /set
+error-key-changed = La clé ne peut pas être modifiée pendant l'édition, renommez la note avec /rename
+error-revision-not-found = La version { $revision } de cette note n’est plus conservée
+error-unexpected = Une erreur s'est produite de notre côté
+error-reported = Une erreur s'est produite de notre côté. Si cela se reproduit, signalez l'identifiant d'erreur `{ $id }`
+
+## Commands
+command-set = enregistrer
+command-set-description = Enregistrer une nouvelle note. Ouvre un éditeur si la valeur est omise
+command-set-key = clé
+command-set-key-description = Clé de la note
+command-set-value = valeur
+command-set-value-description = Texte de la note
+command-set-file = fichier
+command-set-file-description = Fichier texte à enregistrer au lieu de la valeur
+command-set-global = global
+command-set-global-description = Enregistrer dans les notes globales des administrateurs du bot
+command-set-visibility = visibilite
+command-set-visibility-description = Qui d’autre peut lire la note
+command-set-visibility-private = Moi seulement
+command-set-visibility-guild = Membres de ce serveur
+command-set-visibility-public = Tout le monde
+command-set-ttl = ttl
+command-set-ttl-description = Minutes avant l'expiration de la note, 0 n'expire jamais
+command-get = lire
+command-get-description = Lire une note enregistrée
+command-get-key = clé
+command-get-key-description = Clé de la note
+command-get-as-file = en_fichier
+command-get-as-file-description = Envoyer la note en fichier texte
+command-get-global = global
+command-get-global-description = Lire dans les notes globales des administrateurs du bot
+command-echo = écho
+command-echo-description = Répondre avec le même texte
+command-echo-text = texte
+command-echo-text-description = Texte de la réponse
+command-ls = ls
+command-ls-description = Lister toutes les notes disponibles
+command-ls-global = global
+command-ls-global-description = Lister les notes globales des administrateurs du bot
+command-edit = modifier
+command-edit-description = Modifier une note dans un éditeur prérempli
+command-edit-key = clé
+command-edit-key-description = Clé de la note
+command-edit-global = global
+command-edit-global-description = Modifier une note globale des administrateurs du bot
+command-rename = renommer
+command-rename-description = Donner une nouvelle clé à une note
+command-rename-key = clé
+command-rename-key-description = Clé actuelle de la note
+command-rename-new-key = nouvelle_clé
+command-rename-new-key-description = Nouvelle clé de la note
+command-rename-global = global
+command-rename-global-description = Renommer une note globale des administrateurs du bot
+command-del = supprimer
+command-del-description = Supprimer une note après confirmation
+command-del-key = clé
+command-del-key-description = Clé de la note
+command-del-global = global
+command-del-global-description = Supprimer une note globale des administrateurs du bot
+command-history = historique
+command-history-description = Afficher les versions précédentes d’une note
+command-history-key = clé
+command-history-key-description = Clé de la note
+command-history-global = global
+command-history-global-description = Afficher une note globale des administrateurs du bot
+command-diff = comparer
+command-diff-description = Afficher les changements entre deux versions d’une note
+command-diff-key = clé
+command-diff-key-description = Clé de la note
+command-diff-from = de
+command-diff-from-description = Version plus ancienne
+command-diff-to = à
+command-diff-to-description = Version plus récente, l’actuelle par défaut
+command-diff-global = global
+command-diff-global-description = Comparer une note globale des administrateurs du bot
+command-restore = restaurer
+command-restore-description = Faire d’une version précédente la valeur actuelle d’une note
+command-restore-key = clé
+command-restore-key-description = Clé de la note
+command-restore-revision = version
+command-restore-revision-description = Version à restaurer, voir /historique
+command-restore-global = global
+command-restore-global-description = Restaurer une note globale des administrateurs du bot
+command-save-as-note = Enregistrer comme note
+command-notes-by-user = Notes de l'utilisateur
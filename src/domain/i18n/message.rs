@@ -0,0 +1,307 @@
+use std::collections::HashMap;
+use std::fmt::Write;
+
+use super::Arg;
+
+/// Parse failure of a message file, `line` starts at 1
+#[derive(Debug, thiserror::Error, PartialEq)]
+#[error("line {}: {}", line, reason)]
+pub struct ParseError {
+    pub line: usize,
+    pub reason: &'static str,
+}
+
+/// Messages of one locale
+#[derive(Debug, Clone, Default)]
+pub struct Bundle {
+    messages: HashMap<String, Pattern>,
+}
+
+impl Bundle {
+    /// Parses the subset of Fluent syntax the bot uses:
+    /// `id = text` with indented continuation lines, `# comments`,
+    /// `{ $variable }` placeables and `{ $variable -> [one] … *[other] … }` selectors.
+    pub fn parse(source: &str) -> Result<Self, ParseError> {
+        let mut messages = HashMap::new();
+        let mut current: Option<(String, String, usize)> = None;
+        for (index, line) in source.lines().enumerate() {
+            let line_number = index + 1;
+            // closing brace of a selector may be unindented
+            let continues = line.starts_with(char::is_whitespace) || line.starts_with('}');
+            if continues && current.is_some() {
+                if let Some((_, value, _)) = current.as_mut() {
+                    if !line.trim().is_empty() {
+                        if !value.is_empty() {
+                            value.push('\n');
+                        }
+                        value.push_str(line.trim());
+                    }
+                }
+                continue;
+            }
+            if let Some((id, value, start)) = current.take() {
+                messages.insert(id, Pattern::parse(&value, start)?);
+            }
+            if line.trim().is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (id, value) = line.split_once('=').ok_or(ParseError {
+                line: line_number,
+                reason: "expected `id = value`",
+            })?;
+            let id = id.trim();
+            if !is_identifier(id) {
+                return Err(ParseError { line: line_number, reason: "invalid message id" });
+            }
+            if messages.contains_key(id) {
+                return Err(ParseError { line: line_number, reason: "duplicate message id" });
+            }
+            current = Some((id.to_owned(), value.trim().to_owned(), line_number));
+        }
+        if let Some((id, value, start)) = current {
+            messages.insert(id, Pattern::parse(&value, start)?);
+        }
+        Ok(Bundle { messages })
+    }
+
+    pub fn get(&self, id: &str) -> Option<&Pattern> {
+        self.messages.get(id)
+    }
+
+    #[cfg(test)]
+    pub fn ids(&self) -> impl Iterator<Item = &str> {
+        self.messages.keys().map(String::as_str)
+    }
+}
+
+fn is_identifier(text: &str) -> bool {
+    let mut chars = text.chars();
+    chars.next().is_some_and(|c| c.is_ascii_alphabetic())
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+#[derive(Debug, Clone)]
+pub struct Pattern(Vec<Element>);
+
+#[derive(Debug, Clone)]
+enum Element {
+    Text(String),
+    Variable(String),
+    Select {
+        selector: String,
+        variants: Vec<(String, Pattern)>,
+        default: usize,
+    },
+}
+
+impl Pattern {
+    fn parse(source: &str, line: usize) -> Result<Self, ParseError> {
+        let mut parser = Parser { chars: source.chars().collect(), position: 0, line };
+        let pattern = parser.pattern(false)?;
+        match parser.peek() {
+            None => Ok(pattern),
+            Some(_) => Err(parser.error("unexpected `}`")),
+        }
+    }
+
+    /// Fills in `args`, `plural` picks the category of numbers for selectors
+    pub fn format(&self, args: &[(&str, Arg)], plural: &dyn Fn(i64) -> &'static str) -> String {
+        let mut output = String::new();
+        self.write(&mut output, args, plural);
+        output
+    }
+
+    fn write(&self, output: &mut String, args: &[(&str, Arg)], plural: &dyn Fn(i64) -> &'static str) {
+        for element in &self.0 {
+            match element {
+                Element::Text(text) => output.push_str(text),
+                Element::Variable(name) => match lookup(args, name) {
+                    Some(Arg::Text(text)) => output.push_str(text),
+                    Some(Arg::Number(number)) => {
+                        let _ = write!(output, "{}", number);
+                    }
+                    None => {
+                        let _ = write!(output, "{{${}}}", name);
+                    }
+                },
+                Element::Select { selector, variants, default } => {
+                    let key = |key: &str| variants.iter().position(|(k, _)| k == key);
+                    let chosen = match lookup(args, selector) {
+                        Some(Arg::Number(number)) => key(&number.to_string()).or_else(|| key(plural(*number))),
+                        Some(Arg::Text(text)) => key(text),
+                        None => None,
+                    };
+                    variants[chosen.unwrap_or(*default)].1.write(output, args, plural);
+                }
+            }
+        }
+    }
+}
+
+fn lookup<'a>(args: &'a [(&str, Arg)], name: &str) -> Option<&'a Arg> {
+    args.iter().find(|(n, _)| *n == name).map(|(_, arg)| arg)
+}
+
+struct Parser {
+    chars: Vec<char>,
+    position: usize,
+    /// Line of the message start, for errors
+    line: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.position).copied()
+    }
+
+    fn error(&self, reason: &'static str) -> ParseError {
+        let line = self.line + self.chars[..self.position.min(self.chars.len())].iter().filter(|c| **c == '\n').count();
+        ParseError { line, reason }
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.position += 1;
+        }
+    }
+
+    fn expect(&mut self, expected: char, reason: &'static str) -> Result<(), ParseError> {
+        if self.peek() == Some(expected) {
+            self.position += 1;
+            Ok(())
+        } else {
+            Err(self.error(reason))
+        }
+    }
+
+    /// Text and placeables up to `}`, or up to the end of the line for variants
+    fn pattern(&mut self, variant: bool) -> Result<Pattern, ParseError> {
+        let mut elements = vec![];
+        let mut text = String::new();
+        while let Some(c) = self.peek() {
+            match c {
+                '}' => break,
+                '\n' if variant => break,
+                '{' => {
+                    if !text.is_empty() {
+                        elements.push(Element::Text(std::mem::take(&mut text)));
+                    }
+                    self.position += 1;
+                    elements.push(self.placeable()?);
+                }
+                c => {
+                    text.push(c);
+                    self.position += 1;
+                }
+            }
+        }
+        if !text.is_empty() {
+            elements.push(Element::Text(text));
+        }
+        Ok(Pattern(elements))
+    }
+
+    fn placeable(&mut self) -> Result<Element, ParseError> {
+        self.skip_whitespace();
+        if self.peek() == Some('"') {
+            self.position += 1;
+            let mut literal = String::new();
+            while let Some(c) = self.peek().filter(|c| *c != '"') {
+                literal.push(c);
+                self.position += 1;
+            }
+            self.expect('"', "unterminated string literal")?;
+            self.skip_whitespace();
+            self.expect('}', "expected `}`")?;
+            return Ok(Element::Text(literal));
+        }
+        self.expect('$', "expected `$variable` or string literal")?;
+        let name = self.identifier()?;
+        self.skip_whitespace();
+        if self.peek() == Some('}') {
+            self.position += 1;
+            return Ok(Element::Variable(name));
+        }
+        self.expect('-', "expected `}` or `->`")?;
+        self.expect('>', "expected `->`")?;
+        let mut variants = vec![];
+        let mut default = None;
+        loop {
+            self.skip_whitespace();
+            match self.peek() {
+                Some('}') => {
+                    self.position += 1;
+                    break;
+                }
+                Some('*') => {
+                    if default.is_some() {
+                        return Err(self.error("more than one default variant"));
+                    }
+                    default = Some(variants.len());
+                    self.position += 1;
+                }
+                _ => {}
+            }
+            self.expect('[', "expected `[variant]`")?;
+            self.skip_whitespace();
+            let key = self.identifier()?;
+            self.skip_whitespace();
+            self.expect(']', "expected `]`")?;
+            while self.peek() == Some(' ') {
+                self.position += 1;
+            }
+            variants.push((key, self.pattern(true)?));
+        }
+        let default = default.ok_or_else(|| self.error("missing default variant"))?;
+        Ok(Element::Select { selector: name, variants, default })
+    }
+
+    fn identifier(&mut self) -> Result<String, ParseError> {
+        let start = self.position;
+        while self.peek().is_some_and(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_') {
+            self.position += 1;
+        }
+        if start == self.position {
+            return Err(self.error("expected identifier"));
+        }
+        Ok(self.chars[start..self.position].iter().collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Bundle, ParseError};
+    use crate::domain::i18n::Arg;
+
+    fn english(n: i64) -> &'static str {
+        if n == 1 { "one" } else { "other" }
+    }
+
+    #[test]
+    fn multiline_select() {
+        let bundle = Bundle::parse(
+            "# comment\n\
+             try-again = Try again in { $seconds ->\n    [0] a moment\n    [one] { $seconds } second\n   *[other] { $seconds } seconds\n}\n\
+             greeting = Hello, { $name }!\n",
+        )
+        .unwrap();
+        let format = |seconds: i64| {
+            bundle.get("try-again").unwrap().format(&[("seconds", Arg::Number(seconds))], &english)
+        };
+        assert_eq!(format(0), "Try again in a moment");
+        assert_eq!(format(1), "Try again in 1 second");
+        assert_eq!(format(3), "Try again in 3 seconds");
+        assert_eq!(
+            bundle.get("greeting").unwrap().format(&[("name", Arg::from("Ada"))], &english),
+            "Hello, Ada!"
+        );
+        assert_eq!(bundle.get("greeting").unwrap().format(&[], &english), "Hello, {$name}!");
+    }
+
+    #[test]
+    fn errors_point_at_line() {
+        let error = Bundle::parse("ok = fine\n\nbroken = { $x ->\n  [one] one\n}\n").unwrap_err();
+        assert_eq!(error, ParseError { line: 5, reason: "missing default variant" });
+        assert!(Bundle::parse("no value here").is_err());
+    }
+}
@@ -0,0 +1,255 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use log::warn;
+
+use crate::discord::interaction::Interaction;
+use crate::discord::Locale;
+
+mod message;
+
+pub use message::{Bundle, ParseError};
+
+/// Message files compiled into the binary, the image has no filesystem to load them from
+const BUNDLED: [(&str, &str); 4] = [
+    ("en", include_str!("locales/en.ftl")),
+    ("de", include_str!("locales/de.ftl")),
+    ("es", include_str!("locales/es.ftl")),
+    ("fr", include_str!("locales/fr.ftl")),
+];
+
+/// Language used when none of the user's languages has a message
+pub const DEFAULT_LANGUAGE: &str = "en";
+
+#[derive(Debug, thiserror::Error)]
+#[error("Invalid messages for {}, {}", code, source)]
+pub struct CatalogError {
+    code: String,
+    #[source]
+    source: ParseError,
+}
+
+/// Value of a message argument
+#[derive(Debug, Clone)]
+pub enum Arg {
+    Text(String),
+    /// Numbers also select plural variants
+    Number(i64),
+}
+
+impl From<&str> for Arg {
+    fn from(text: &str) -> Self {
+        Arg::Text(text.to_owned())
+    }
+}
+
+impl From<String> for Arg {
+    fn from(text: String) -> Self {
+        Arg::Text(text)
+    }
+}
+
+impl From<i64> for Arg {
+    fn from(number: i64) -> Self {
+        Arg::Number(number)
+    }
+}
+
+impl From<u64> for Arg {
+    fn from(number: u64) -> Self {
+        Arg::Number(number.try_into().unwrap_or(i64::MAX))
+    }
+}
+
+impl From<usize> for Arg {
+    fn from(number: usize) -> Self {
+        Arg::Number(number.try_into().unwrap_or(i64::MAX))
+    }
+}
+
+/// Messages of all languages, keyed by locale code (`pt-BR`) or language (`pt`).
+/// Cheap to clone.
+#[derive(Debug, Clone)]
+pub struct Catalog {
+    bundles: Arc<HashMap<String, Bundle>>,
+}
+
+impl Catalog {
+    pub fn new() -> Self {
+        Catalog { bundles: Arc::new(HashMap::new()) }
+    }
+
+    /// Catalog of the message files shipped with the bot
+    pub fn bundled() -> Result<Self, CatalogError> {
+        BUNDLED
+            .iter()
+            .try_fold(Catalog::new(), |catalog, (code, source)| catalog.with_messages(code, source))
+    }
+
+    pub fn with_messages(mut self, code: &str, source: &str) -> Result<Self, CatalogError> {
+        let bundle = Bundle::parse(source).map_err(|source| CatalogError {
+            code: code.to_owned(),
+            source,
+        })?;
+        Arc::make_mut(&mut self.bundles).insert(code.to_owned(), bundle);
+        Ok(self)
+    }
+
+    pub fn bundle(&self, code: &str) -> Option<&Bundle> {
+        self.bundles.get(code)
+    }
+
+    /// Message `id` in every Discord locale which translates it differently than the default language
+    pub fn localizations(&self, id: &str) -> HashMap<Locale, String> {
+        let default = Translator::new(self.clone(), []).text(id);
+        Locale::ALL
+            .iter()
+            .map(|locale| (locale.clone(), Translator::new(self.clone(), [locale]).text(id)))
+            .filter(|(_, text)| *text != default)
+            .collect()
+    }
+
+    /// Translator for the user's locale, falling back to the guild's
+    pub fn translator(&self, interaction: &Interaction) -> Translator {
+        let locales = [interaction.locale.as_ref(), interaction.guild_locale.as_ref()];
+        Translator::new(self.clone(), locales.into_iter().flatten())
+    }
+}
+
+impl Default for Catalog {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Looks up messages for one interaction.
+///
+/// Each locale is tried by its full code, its parents (`en-GB` → `en-US`),
+/// then by its language, then the default language.
+#[derive(Debug, Clone)]
+pub struct Translator {
+    catalog: Catalog,
+    chain: Vec<String>,
+}
+
+impl Translator {
+    pub fn new<'a>(catalog: Catalog, locales: impl IntoIterator<Item = &'a Locale>) -> Self {
+        let mut chain: Vec<String> = vec![];
+        let codes = locales
+            .into_iter()
+            .flat_map(|locale| {
+                let language = locale.language().to_owned();
+                locale.fallbacks().map(|l| l.code().to_owned()).chain([language])
+            })
+            .chain([DEFAULT_LANGUAGE.to_owned()]);
+        for code in codes {
+            if !chain.contains(&code) {
+                chain.push(code);
+            }
+        }
+        Translator { catalog, chain }
+    }
+
+    /// Message `id` with `args` filled in. Unknown messages are logged and replaced by their id.
+    pub fn message(&self, id: &str, args: &[(&str, Arg)]) -> String {
+        let found = self.chain.iter().find_map(|code| {
+            let pattern = self.catalog.bundle(code)?.get(id)?;
+            Some((code, pattern))
+        });
+        match found {
+            Some((code, pattern)) => {
+                let language = code.split('-').next().unwrap_or_default();
+                pattern.format(args, &|n| plural_category(language, n))
+            }
+            None => {
+                warn!("Missing message {} for {:?}", id, self.chain);
+                id.to_owned()
+            }
+        }
+    }
+
+    /// Shorthand for messages without arguments
+    pub fn text(&self, id: &str) -> String {
+        self.message(id, &[])
+    }
+}
+
+/// English messages of the bundled catalog
+impl Default for Translator {
+    fn default() -> Self {
+        let catalog = Catalog::bundled().expect("bundled messages are valid");
+        Translator::new(catalog, [])
+    }
+}
+
+/// CLDR plural category of integers, simplified to the languages Discord supports
+fn plural_category(language: &str, n: i64) -> &'static str {
+    let n = n.unsigned_abs();
+    let (ones, tens) = (n % 10, n % 100);
+    match language {
+        "ja" | "ko" | "th" | "vi" | "zh" | "id" => "other",
+        "fr" | "pt" | "hi" if n <= 1 => "one",
+        "ru" | "uk" | "pl" if ones == 1 && tens != 11 && (language != "pl" || n == 1) => "one",
+        "ru" | "uk" | "pl" if (2..=4).contains(&ones) && !(12..=14).contains(&tens) => "few",
+        "ru" | "uk" | "pl" => "many",
+        _ if n == 1 => "one",
+        _ => "other",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{plural_category, Arg, Catalog, Translator, BUNDLED, DEFAULT_LANGUAGE};
+    use crate::discord::Locale;
+
+    #[test]
+    fn falls_back_to_language_then_default() {
+        let catalog = Catalog::new()
+            .with_messages("en", "hello = Hello\nbye = Bye")
+            .unwrap()
+            .with_messages("pt", "hello = Olá")
+            .unwrap()
+            .with_messages("pt-BR", "hello = Oi")
+            .unwrap();
+        let brazilian = Translator::new(catalog.clone(), [&Locale::PortugueseBrazilian]);
+        assert_eq!(brazilian.text("hello"), "Oi");
+        assert_eq!(brazilian.text("bye"), "Bye");
+        assert_eq!(brazilian.text("missing"), "missing");
+
+        let french = Translator::new(catalog.clone(), [&Locale::French]);
+        assert_eq!(french.text("hello"), "Hello");
+
+        let catalog = catalog.with_messages("en-US", "hello = Howdy").unwrap();
+        let british = Translator::new(catalog, [&Locale::EnglishUk]);
+        assert_eq!(british.text("hello"), "Howdy");
+    }
+
+    #[test]
+    fn plural_rules() {
+        assert_eq!(plural_category("en", 0), "other");
+        assert_eq!(plural_category("fr", 0), "one");
+        assert_eq!(plural_category("pl", 22), "few");
+        assert_eq!(plural_category("pl", 25), "many");
+        assert_eq!(plural_category("ru", 21), "one");
+    }
+
+    #[test]
+    fn bundled_languages_have_all_messages() {
+        let catalog = Catalog::bundled().unwrap();
+        let default = catalog.bundle(DEFAULT_LANGUAGE).unwrap();
+        for (code, _) in BUNDLED {
+            let bundle = catalog.bundle(code).unwrap();
+            for id in default.ids() {
+                assert!(bundle.get(id).is_some(), "{} misses {}", code, id);
+            }
+            for id in bundle.ids() {
+                assert!(default.get(id).is_some(), "{} has unknown {}", code, id);
+            }
+        }
+        let german = Translator::new(catalog, [&Locale::German]);
+        assert_eq!(
+            german.message("try-again", &[("seconds", Arg::from(1u64))]),
+            "Zu viele Anfragen, versuche es in 1 Sekunde erneut"
+        );
+    }
+}
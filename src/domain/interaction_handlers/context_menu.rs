@@ -0,0 +1,246 @@
+use std::future::{Future, ready};
+use futures_util::FutureExt;
+use crate::discord::interaction::{Interaction, InteractionData, InteractionType};
+use crate::discord::rest::application_command::ApplicationCommandType;
+use crate::discord::Permissions;
+use crate::domain::command_handlers::{CommandHandlerResult, MessageCommandHandler, UserCommandHandler, UserCommandTarget};
+use crate::domain::interaction_pipeline::{InteractionError, InteractionHandler, InteractionHandlerResult, Route, Task};
+
+fn context_menu_data<'i>(
+    interaction: &'i Interaction,
+    command_type: ApplicationCommandType,
+    name: &str,
+) -> Option<&'i InteractionData> {
+    Some(interaction)
+        .filter(|i| i.interaction_type == InteractionType::ApplicationCommand)
+        .and_then(|i| i.data.as_ref())
+        .filter(|d| d.command_type == Some(command_type) && d.name == name)
+}
+
+pub struct MessageCommandInteractionHandler<T>(T);
+
+impl<MH, C, F> InteractionHandler<C> for MessageCommandInteractionHandler<MH>
+    where MH: MessageCommandHandler<C, Future=F>,
+          F: Future<Output=CommandHandlerResult> + 'static
+{
+    type Future = Task<InteractionHandlerResult>;
+
+    fn handle(&self, interaction: &Interaction, context: &C) -> Self::Future {
+        let target = context_menu_data(
+            interaction,
+            ApplicationCommandType::Message,
+            <MH as MessageCommandHandler<C>>::name(),
+        )
+        .map(|d| d.target_message().cloned().ok_or(InteractionError::InvalidCommand));
+        match target {
+            Some(Ok(message)) => Box::pin(self.0.handle(message, context).map(Some)),
+            Some(Err(e)) => Box::pin(ready(Some(Err(e)))),
+            None => Box::pin(ready(None)),
+        }
+    }
+
+    fn route(&self) -> Route {
+        Route::Command {
+            command_type: ApplicationCommandType::Message,
+            name: <MH as MessageCommandHandler<C>>::name().to_owned(),
+        }
+    }
+
+    fn required_permissions(&self) -> Permissions {
+        <MH as MessageCommandHandler<C>>::required_permissions()
+    }
+
+    fn required_bot_permissions(&self) -> Permissions {
+        <MH as MessageCommandHandler<C>>::required_bot_permissions()
+    }
+}
+
+impl<T> From<T> for MessageCommandInteractionHandler<T> {
+    fn from(e: T) -> Self {
+        MessageCommandInteractionHandler(e)
+    }
+}
+
+pub struct UserCommandInteractionHandler<T>(T);
+
+impl<UH, C, F> InteractionHandler<C> for UserCommandInteractionHandler<UH>
+    where UH: UserCommandHandler<C, Future=F>,
+          F: Future<Output=CommandHandlerResult> + 'static
+{
+    type Future = Task<InteractionHandlerResult>;
+
+    fn handle(&self, interaction: &Interaction, context: &C) -> Self::Future {
+        let target = context_menu_data(
+            interaction,
+            ApplicationCommandType::User,
+            <UH as UserCommandHandler<C>>::name(),
+        )
+        .map(|d| {
+            d.target_user()
+                .map(|(user, member)| UserCommandTarget {
+                    user: user.clone(),
+                    member: member.cloned(),
+                })
+                .ok_or(InteractionError::InvalidCommand)
+        });
+        match target {
+            Some(Ok(target)) => Box::pin(self.0.handle(target, context).map(Some)),
+            Some(Err(e)) => Box::pin(ready(Some(Err(e)))),
+            None => Box::pin(ready(None)),
+        }
+    }
+
+    fn route(&self) -> Route {
+        Route::Command {
+            command_type: ApplicationCommandType::User,
+            name: <UH as UserCommandHandler<C>>::name().to_owned(),
+        }
+    }
+
+    fn required_permissions(&self) -> Permissions {
+        <UH as UserCommandHandler<C>>::required_permissions()
+    }
+
+    fn required_bot_permissions(&self) -> Permissions {
+        <UH as UserCommandHandler<C>>::required_bot_permissions()
+    }
+}
+
+impl<T> From<T> for UserCommandInteractionHandler<T> {
+    fn from(e: T) -> Self {
+        UserCommandInteractionHandler(e)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::future::{ready, Ready};
+
+    use serde_json::json;
+
+    use super::{MessageCommandInteractionHandler, UserCommandInteractionHandler};
+    use crate::discord::interaction::message::Message;
+    use crate::discord::interaction::{Interaction, InteractionCallback, InteractionCallbackMessage};
+    use crate::domain::bot::Get;
+    use crate::domain::command_handlers::{CommandHandlerResult, MessageCommandHandler, UserCommandHandler, UserCommandTarget};
+    use crate::domain::i18n::Translator;
+    use crate::domain::interaction_pipeline::{InteractionHandler, InteractionResponse};
+
+    struct TestContext(Translator);
+
+    impl Get<Translator> for TestContext {
+        fn get(&self) -> &Translator {
+            &self.0
+        }
+    }
+
+    struct QuoteHandler;
+
+    impl<C> MessageCommandHandler<C> for QuoteHandler {
+        type Future = Ready<CommandHandlerResult>;
+
+        fn name() -> &'static str {
+            "Quote"
+        }
+
+        fn handle(&self, message: Message, _: &C) -> Self::Future {
+            let message = InteractionCallbackMessage {
+                content: Some(format!("> {}", message.content)),
+                ..Default::default()
+            };
+            ready(Ok(InteractionCallback::channel_message_with_source(message).into()))
+        }
+    }
+
+    struct GreetHandler;
+
+    impl<C> UserCommandHandler<C> for GreetHandler {
+        type Future = Ready<CommandHandlerResult>;
+
+        fn name() -> &'static str {
+            "Greet"
+        }
+
+        fn handle(&self, target: UserCommandTarget, _: &C) -> Self::Future {
+            let name = target.member.as_ref().and_then(|member| member.display_name()).unwrap_or("nobody");
+            let message = InteractionCallbackMessage {
+                content: Some(format!("Hello {} ({})", name, target.user.mention())),
+                ..Default::default()
+            };
+            ready(Ok(InteractionCallback::channel_message_with_source(message).into()))
+        }
+    }
+
+    fn message_command(name: &str, command_type: u8) -> Interaction {
+        serde_json::from_value(json!({
+            "application_id": "1",
+            "id": "2",
+            "token": "token",
+            "type": 2,
+            "data": {
+                "id": "3",
+                "name": name,
+                "type": command_type,
+                "target_id": "10",
+                "resolved": {"messages": {"10": {
+                    "id": "10",
+                    "channel_id": "11",
+                    "author": {"id": "12", "username": "author"},
+                    "content": "hello",
+                    "timestamp": "2022-06-01T12:00:00+00:00"
+                }}}
+            },
+            "version": 1
+        }))
+        .unwrap()
+    }
+
+    #[actix_rt::test]
+    async fn message_command_receives_target_message() {
+        let handler = MessageCommandInteractionHandler::from(QuoteHandler);
+        let result = handler.handle(&message_command("Quote", 3), &TestContext(Translator::default())).await;
+        match result {
+            Some(Ok(InteractionResponse::Callback(callback))) => {
+                assert_eq!(callback.into_message().unwrap().content.as_deref(), Some("> hello"));
+            }
+            _ => panic!("message command was not handled"),
+        }
+    }
+
+    #[actix_rt::test]
+    async fn chat_input_command_with_same_name_is_ignored() {
+        let handler = MessageCommandInteractionHandler::from(QuoteHandler);
+        assert!(handler.handle(&message_command("Quote", 1), &TestContext(Translator::default())).await.is_none());
+    }
+
+    #[actix_rt::test]
+    async fn user_command_receives_target_user() {
+        let interaction: Interaction = serde_json::from_value(json!({
+            "application_id": "1",
+            "id": "2",
+            "token": "token",
+            "type": 2,
+            "guild_id": "4",
+            "data": {
+                "id": "3",
+                "name": "Greet",
+                "type": 2,
+                "target_id": "12",
+                "resolved": {
+                    "users": {"12": {"id": "12", "username": "target", "global_name": "Target"}},
+                    "members": {"12": {"nick": "Nick", "roles": []}}
+                }
+            },
+            "version": 1
+        }))
+        .unwrap();
+        let handler = UserCommandInteractionHandler::from(GreetHandler);
+        match handler.handle(&interaction, &TestContext(Translator::default())).await {
+            Some(Ok(InteractionResponse::Callback(callback))) => {
+                assert_eq!(callback.into_message().unwrap().content.as_deref(), Some("Hello Nick (<@12>)"));
+            }
+            _ => panic!("user command was not handled"),
+        }
+        assert!(handler.handle(&message_command("Greet", 3), &TestContext(Translator::default())).await.is_none());
+    }
+}
@@ -0,0 +1,139 @@
+use crate::discord::interaction::component::{ActionRow, Button};
+use crate::discord::interaction::{InteractionCallback, InteractionCallbackMessage, InteractionData};
+use crate::domain::bot::Get;
+use crate::domain::command_handlers::{CommandHandler, CommandHandlerResult};
+use crate::domain::component_handlers::{custom_id, ComponentHandler, ComponentHandlerResult};
+use crate::domain::i18n::{Arg, Translator};
+use crate::domain::interaction_pipeline::{InteractionError, Task};
+use crate::domain::namespace::{Notes, Scope};
+
+const DEL_CONFIRM_ID: &str = "del";
+const CANCEL: &str = "cancel";
+
+pub struct DelCommandHandler;
+
+pub struct DelCommandArgs {
+    key: String,
+    scope: Scope,
+}
+
+/// `/del` asks for confirmation before anything is deleted
+impl<C: Get<Notes> + Get<Translator>> CommandHandler<C> for DelCommandHandler {
+    type Args = DelCommandArgs;
+    type Future = Task<CommandHandlerResult>;
+
+    fn name() -> &'static str {
+        "del"
+    }
+
+    fn parse_args(interaction_data: &InteractionData) -> Option<Self::Args> {
+        let key = interaction_data.option("key")?.as_str()?.to_string();
+        let scope = Scope::from_options(interaction_data)?;
+        Some(DelCommandArgs { key, scope })
+    }
+
+    fn handle(&self, args: Self::Args, context: &C) -> Self::Future {
+        let notes: Notes = Get::<Notes>::get(context).clone();
+        let translator: Translator = Get::<Translator>::get(context).clone();
+        Box::pin(async move {
+            // deny right away instead of after the confirmation
+            notes.editable(args.scope, &args.key).await?;
+            let confirm = Button::danger(
+                &args.scope.custom_id(DEL_CONFIRM_ID, &key_digest(&args.key)),
+                &translator.text("del-confirm-button"),
+            );
+            let cancel = Button::secondary(&custom_id(DEL_CONFIRM_ID, CANCEL), &translator.text("del-cancel-button"));
+            let message = InteractionCallbackMessage {
+                content: Some(translator.message("del-confirm", &[("key", Arg::from(args.key.as_str()))])),
+                components: Some(Box::new([ActionRow::new().with_component(confirm).with_component(cancel).into()])),
+                ..Default::default()
+            };
+            Ok(InteractionCallback::channel_message_with_source(message.ephemeral()).into())
+        })
+    }
+}
+
+/// Buttons of the `/del` confirmation.
+/// Keys may be too long for a custom id, so the confirm button carries a digest of the key.
+pub struct DelConfirmComponentHandler;
+
+pub enum DelConfirmArgs {
+    Confirm { scope: Scope, digest: String },
+    Cancel,
+}
+
+impl<C: Get<Notes> + Get<Translator>> ComponentHandler<C> for DelConfirmComponentHandler {
+    type Args = DelConfirmArgs;
+    type Future = Task<ComponentHandlerResult>;
+
+    fn custom_id_prefix() -> &'static str {
+        DEL_CONFIRM_ID
+    }
+
+    fn parse_args(custom_id_args: &str, _: &InteractionData) -> Option<Self::Args> {
+        match Scope::split_custom_id_args(custom_id_args) {
+            (Scope::Local, CANCEL) => Some(DelConfirmArgs::Cancel),
+            (_, "") => None,
+            (scope, digest) => Some(DelConfirmArgs::Confirm { scope, digest: digest.to_owned() }),
+        }
+    }
+
+    fn handle(&self, args: Self::Args, context: &C) -> Self::Future {
+        let notes: Notes = Get::<Notes>::get(context).clone();
+        let translator: Translator = Get::<Translator>::get(context).clone();
+        Box::pin(async move {
+            let content = match args {
+                DelConfirmArgs::Cancel => translator.text("del-cancelled"),
+                DelConfirmArgs::Confirm { scope, digest } => {
+                    let key = notes
+                        .keys(scope)
+                        .await?
+                        .into_iter()
+                        .find(|key| key_digest(key) == digest)
+                        .ok_or(InteractionError::KeyNotFound { key: None })?;
+                    notes.delete(scope, &key).await?;
+                    translator.message("note-deleted", &[("key", Arg::from(key))])
+                }
+            };
+            let message = InteractionCallbackMessage {
+                content: Some(content),
+                components: Some(Box::new([])),
+                ..Default::default()
+            };
+            Ok(InteractionCallback::update_message(message).into())
+        })
+    }
+}
+
+/// FNV-1a hash of `key` in hex, stable across restarts unlike `DefaultHasher`
+fn key_digest(key: &str) -> String {
+    let hash = key
+        .bytes()
+        .fold(0xcbf29ce484222325u64, |hash, byte| (hash ^ u64::from(byte)).wrapping_mul(0x100000001b3));
+    format!("{:016x}", hash)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{key_digest, DelConfirmArgs, DelConfirmComponentHandler};
+    use crate::domain::bot::BotContext;
+    use crate::domain::component_handlers::ComponentHandler;
+    use crate::domain::namespace::Scope;
+    use crate::discord::interaction::InteractionData;
+    use serde_json::json;
+
+    #[test]
+    fn confirm_button_fits_any_key() {
+        let key = "k".repeat(500);
+        let id = Scope::Global.custom_id("del", &key_digest(&key));
+        assert!(id.len() <= 100);
+        assert_eq!(key_digest("todo"), key_digest("todo"));
+        assert_ne!(key_digest("todo"), key_digest("done"));
+
+        let data: InteractionData = serde_json::from_value(json!({"id": "1", "name": ""})).unwrap();
+        let parse = |args| <DelConfirmComponentHandler as ComponentHandler<BotContext>>::parse_args(args, &data);
+        assert!(matches!(parse("cancel"), Some(DelConfirmArgs::Cancel)));
+        assert!(matches!(parse("global:00ff"), Some(DelConfirmArgs::Confirm { scope: Scope::Global, .. })));
+        assert!(parse("global").is_none());
+    }
+}
@@ -1,5 +1,5 @@
 use crate::discord::rest::application_command::ApplicationCommandOptionValue::Str;
-use crate::discord::rest::application_command::ApplicationCommandType;
+use crate::discord::rest::application_command::ApplicationCommandOptionType;
 use crate::domain::command_handlers::{CommandHandlerResult, NoContextCommandHandler};
 use crate::domain::interaction_pipeline::Task;
 use std::future::ready;
@@ -20,7 +20,7 @@ impl NoContextCommandHandler for EchoCommandHandler {
             .and_then(|o| match o.as_ref() {
                 [ApplicationCommandInteractionDataOption {
                     name: n,
-                    application_command_option_type: ApplicationCommandType::String,
+                    application_command_option_type: ApplicationCommandOptionType::String,
                     value: Str(text),
                 }] if n == "text" => Some(text.to_string()),
                 _ => None,
@@ -29,17 +29,27 @@ impl NoContextCommandHandler for EchoCommandHandler {
     fn handle(&self, args: Self::Args) -> Self::Future {
         let msg = InteractionCallbackMessage {
             content: Some(args),
+            ..Default::default()
         };
         let callback = InteractionCallback::channel_message_with_source(msg);
-        Box::pin(ready(Ok(callback)))
+        Box::pin(ready(Ok(callback.into())))
     }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::discord::interaction::{ApplicationCommandInteractionDataOption, InteractionData};
-    use crate::discord::rest::application_command::{ApplicationCommandOptionValue, ApplicationCommandType};
+    use crate::discord::rest::application_command::{ApplicationCommandOptionType, ApplicationCommandOptionValue, ApplicationCommandType};
+    use crate::discord::interaction::message::MessageFlags;
+    use crate::discord::interaction::Interaction;
+    use crate::discord::rest::mock::MockDiscord;
+    use crate::domain::bot::BotContext;
     use crate::domain::command_handlers::NoContextCommandHandler;
+    use crate::domain::i18n::Catalog;
+    use crate::domain::interaction_handlers::InteractionCommandInteractionHandler;
+    use crate::domain::interaction_pipeline::InteractionPipeline;
+    use crate::domain::store::Storage;
+    use serde_json::json;
     use super::EchoCommandHandler;
 
     #[test]
@@ -51,12 +61,13 @@ mod tests {
         InteractionData {
             options: Some(options.into_boxed_slice()),
 
-            command_type: ApplicationCommandType::SubCommand,
+            command_type: Some(ApplicationCommandType::ChatInput),
             id: Default::default(),
             name: Default::default(),
             resolved: Default::default(),
             guild_id: Default::default(),
             custom_id: Default::default(),
+            component_type: Default::default(),
             values: Default::default(),
             target_id: Default::default(),
             components: Default::default(),
@@ -67,7 +78,7 @@ mod tests {
     fn echo_parse_args() {
         let options = vec![ApplicationCommandInteractionDataOption {
             name: String::from("text"),
-            application_command_option_type: ApplicationCommandType::String,
+            application_command_option_type: ApplicationCommandOptionType::String,
             value: ApplicationCommandOptionValue::Str(String::from("test")),
         }];
 
@@ -81,7 +92,7 @@ mod tests {
     fn echo_fail_parse_args_when_option_name_is_invalid() {
         let options = vec![ApplicationCommandInteractionDataOption {
             name: String::from("some wrong parameter name"),
-            application_command_option_type: ApplicationCommandType::String,
+            application_command_option_type: ApplicationCommandOptionType::String,
             value: ApplicationCommandOptionValue::Str(String::from("test")),
         }];
 
@@ -104,11 +115,11 @@ mod tests {
     fn echo_fail_parse_args_when_more_than_one_option() {
         let options = vec![ApplicationCommandInteractionDataOption {
             name: String::from("text"),
-            application_command_option_type: ApplicationCommandType::String,
+            application_command_option_type: ApplicationCommandOptionType::String,
             value: ApplicationCommandOptionValue::Str(String::from("test")),
         }, ApplicationCommandInteractionDataOption {
             name: String::from("text"),
-            application_command_option_type: ApplicationCommandType::String,
+            application_command_option_type: ApplicationCommandOptionType::String,
             value: ApplicationCommandOptionValue::Str(String::from("test")),
         }];
 
@@ -117,4 +128,42 @@ mod tests {
         let args = EchoCommandHandler::parse_args(&interaction_data);
         assert_eq!(args, None);
     }
+
+    fn echo_interaction(member_permissions: &str) -> Interaction {
+        serde_json::from_value(json!({
+            "application_id": "1",
+            "id": "2",
+            "token": "token",
+            "type": 2,
+            "guild_id": "3",
+            "member": {
+                "user": {"id": "4", "username": "user"},
+                "joined_at": "2022-06-01T12:00:00+00:00",
+                "permissions": member_permissions
+            },
+            "data": {
+                "id": "5",
+                "name": "echo",
+                "type": 1,
+                "options": [{"name": "text", "type": 3, "value": "@everyone hi"}]
+            },
+            "version": 1
+        }))
+        .unwrap()
+    }
+
+    #[actix_rt::test]
+    async fn anyone_can_echo_without_pinging() {
+        let discord = MockDiscord::start();
+        let context = BotContext::new(Storage::memory(), discord.client(), Catalog::bundled().unwrap());
+        let pipeline =
+            InteractionPipeline::new(vec![Box::new(InteractionCommandInteractionHandler::from(EchoCommandHandler))])
+                .unwrap();
+
+        let echoed = pipeline.handle(echo_interaction("0"), &context).await.unwrap().into_message().unwrap();
+        assert_eq!(echoed.content.as_deref(), Some("@everyone hi"));
+        assert!(!echoed.flags.contains(MessageFlags::EPHEMERAL));
+        let allowed_mentions = serde_json::to_value(&echoed.allowed_mentions).unwrap();
+        assert_eq!(allowed_mentions["parse"], json!([]));
+    }
 }
@@ -0,0 +1,44 @@
+use crate::discord::interaction::{InteractionCallback, InteractionData};
+use crate::domain::bot::Get;
+use crate::domain::command_handlers::{CommandHandler, CommandHandlerResult};
+use crate::domain::i18n::Translator;
+use crate::domain::interaction_pipeline::{InteractionError, Task};
+use crate::domain::namespace::{Notes, Scope};
+use super::set::{set_modal, NoteTarget, VALUE_MAX_LENGTH};
+
+pub struct EditCommandHandler;
+
+pub struct EditCommandArgs {
+    key: String,
+    scope: Scope,
+}
+
+/// Opens the set modal prefilled with the current value of the note
+impl<C: Get<Notes> + Get<Translator>> CommandHandler<C> for EditCommandHandler {
+    type Args = EditCommandArgs;
+    type Future = Task<CommandHandlerResult>;
+
+    fn name() -> &'static str {
+        "edit"
+    }
+
+    fn parse_args(interaction_data: &InteractionData) -> Option<Self::Args> {
+        let key = interaction_data.option("key")?.as_str()?.to_string();
+        let scope = Scope::from_options(interaction_data)?;
+        Some(EditCommandArgs { key, scope })
+    }
+
+    fn handle(&self, args: Self::Args, context: &C) -> Self::Future {
+        let notes: Notes = Get::<Notes>::get(context).clone();
+        let translator: Translator = Get::<Translator>::get(context).clone();
+        Box::pin(async move {
+            let note = notes.editable(args.scope, &args.key).await?;
+            // the modal would cut the note short
+            if note.value.chars().count() > VALUE_MAX_LENGTH.into() {
+                return Err(InteractionError::TooLongToEdit { max_length: VALUE_MAX_LENGTH.into() });
+            }
+            let modal = set_modal(NoteTarget::editing(args.scope, &args.key), Some(&args.key), Some(&note.value), &translator);
+            Ok(InteractionCallback::modal(modal).into())
+        })
+    }
+}
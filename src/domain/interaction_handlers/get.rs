@@ -1,44 +1,135 @@
-use crate::discord::rest::application_command::ApplicationCommandOptionValue::Str;
-use crate::discord::rest::application_command::ApplicationCommandType;
 use crate::domain::command_handlers::{
     CommandHandler, CommandHandlerResult,
 };
 use crate::domain::interaction_pipeline::Task;
-use crate::domain::store::Storage;
-use crate::discord::interaction::{ApplicationCommandInteractionDataOption, InteractionCallback, InteractionCallbackMessage, InteractionData};
+use crate::domain::namespace::{Notes, Scope};
+use crate::domain::note::Visibility;
+use crate::discord::interaction::embed::DESCRIPTION_LIMIT;
+use crate::discord::interaction::message::FileUpload;
+use crate::discord::interaction::{InteractionCallback, InteractionCallbackMessage, InteractionData};
+use crate::discord::{Permissions, PermissionsMut};
 use crate::domain::bot::{Get};
+use super::notes::note_embed;
 
 pub struct GetCommandHandler;
 
-impl<C: Get<Storage>> CommandHandler<C> for GetCommandHandler {
-    type Args = String;
+pub struct GetCommandArgs {
+    key: String,
+    as_file: bool,
+    scope: Scope,
+}
+
+impl<C: Get<Notes>> CommandHandler<C> for GetCommandHandler {
+    type Args = GetCommandArgs;
     type Future = Task<CommandHandlerResult>;
 
     fn name() -> &'static str {
         "get"
     }
 
+    /// Long notes are sent as files
+    fn required_bot_permissions() -> Permissions {
+        let mut permissions = PermissionsMut::new();
+        permissions.allow_to_attach_files();
+        permissions.freeze()
+    }
+
     fn parse_args(interaction_data: &InteractionData) -> Option<Self::Args> {
-        interaction_data.options.as_ref()
-            .and_then(|o| match o.as_ref() {
-                [ApplicationCommandInteractionDataOption {
-                    name: key_name,
-                    application_command_option_type: ApplicationCommandType::String,
-                    value: Str(key),
-                }] if key_name == "key" => Some(key.to_string()),
-                _ => None,
-            })
+        let key = interaction_data.option("key")?.as_str()?.to_string();
+        let as_file = match interaction_data.option("as_file") {
+            Some(value) => value.as_bool()?,
+            None => false,
+        };
+        let scope = Scope::from_options(interaction_data)?;
+        Some(GetCommandArgs { key, as_file, scope })
     }
 
     fn handle(&self, args: Self::Args, context: &C) -> Self::Future {
-        let store: Storage = context.get().clone();
+        let notes: Notes = context.get().clone();
         Box::pin(async move {
-            let value = store.read(args.as_str()).await?;
-            let message = InteractionCallbackMessage {
-                content: Some(format!("Your data: `{value}`")),
+            let note = notes.read(args.scope, &args.key).await?;
+            let private = note.visibility == Visibility::Private;
+            let value = note.value;
+            // Notes which do not fit into an embed are always sent as files
+            let message = if args.as_file || value.chars().count() > DESCRIPTION_LIMIT {
+                InteractionCallbackMessage::default().with_file(note_file(&args.key, value))
+            } else {
+                InteractionCallbackMessage {
+                    embeds: Some(Box::new([note_embed(&args.key, &value)?])),
+                    ..Default::default()
+                }
             };
+            // private notes are only shown to their author
+            let message = if private { message.ephemeral() } else { message };
             let callback = InteractionCallback::channel_message_with_source(message);
-            Ok(callback)
+            Ok(callback.into())
         })
     }
 }
+
+fn note_file(key: &str, value: String) -> FileUpload {
+    let name: String = key
+        .chars()
+        .map(|c| if c.is_alphanumeric() || c == '-' || c == '_' { c } else { '_' })
+        .take(100)
+        .collect();
+    FileUpload::new(&format!("{}.txt", name), "text/plain; charset=utf-8", value.into_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{note_file, GetCommandHandler};
+    use crate::discord::interaction::message::MessageFlags;
+    use crate::discord::interaction::Interaction;
+    use crate::discord::rest::mock::MockDiscord;
+    use crate::domain::bot::BotContext;
+    use crate::domain::i18n::Catalog;
+    use crate::domain::interaction_handlers::InteractionCommandInteractionHandler;
+    use crate::domain::interaction_pipeline::InteractionPipeline;
+    use crate::domain::store::Storage;
+    use serde_json::json;
+
+    #[test]
+    fn note_file_name_is_sanitized() {
+        let file = note_file("my notes/2022 draft", String::from("text"));
+        assert_eq!(file.filename, "my_notes_2022_draft.txt");
+        assert_eq!(file.data, b"text");
+    }
+
+    fn get_interaction(app_permissions: &str) -> Interaction {
+        serde_json::from_value(json!({
+            "application_id": "1",
+            "id": "2",
+            "token": "token",
+            "type": 2,
+            "guild_id": "3",
+            "member": {
+                "user": {"id": "4", "username": "user"},
+                "joined_at": "2022-06-01T12:00:00+00:00",
+                "permissions": "0"
+            },
+            "app_permissions": app_permissions,
+            "data": {
+                "id": "5",
+                "name": "get",
+                "type": 1,
+                "options": [{"name": "key", "type": 3, "value": "todo"}]
+            },
+            "version": 1
+        }))
+        .unwrap()
+    }
+
+    #[actix_rt::test]
+    async fn pipeline_refuses_get_when_bot_can_not_attach_files() {
+        let discord = MockDiscord::start();
+        let context = BotContext::new(Storage::memory(), discord.client(), Catalog::bundled().unwrap());
+        let pipeline =
+            InteractionPipeline::new(vec![Box::new(InteractionCommandInteractionHandler::from(GetCommandHandler))])
+                .unwrap();
+
+        let denied = pipeline.handle(get_interaction("0"), &context).await.unwrap().into_message().unwrap();
+        assert_eq!(denied.content.as_deref(), Some("I am missing permissions: **Attach Files**"));
+        assert!(denied.flags.contains(MessageFlags::EPHEMERAL));
+    }
+}
@@ -0,0 +1,130 @@
+use crate::discord::interaction::embed::Embed;
+use crate::discord::interaction::{InteractionCallback, InteractionCallbackMessage, InteractionData};
+use crate::domain::bot::Get;
+use crate::domain::command_handlers::{CommandHandler, CommandHandlerResult};
+use crate::domain::i18n::Translator;
+use crate::domain::interaction_pipeline::{InteractionError, Task};
+use crate::domain::namespace::{Notes, Scope};
+use crate::domain::note::{Note, Visibility};
+use super::notes::{note_diff_embed, note_history_embed, note_restored_embed};
+
+/// Revision number from integer option `name`
+fn revision_option(interaction_data: &InteractionData, name: &str) -> Option<Option<u32>> {
+    match interaction_data.option(name) {
+        Some(value) => Some(Some(value.as_i64()?.try_into().ok()?)),
+        None => Some(None),
+    }
+}
+
+/// Reply with `embed` about `note`, only shown to the author of private notes
+fn embed_message(embed: Embed, note: &Note) -> CommandHandlerResult {
+    let message = InteractionCallbackMessage {
+        embeds: Some(Box::new([embed])),
+        ..Default::default()
+    };
+    let message = if note.visibility == Visibility::Private { message.ephemeral() } else { message };
+    Ok(InteractionCallback::channel_message_with_source(message).into())
+}
+
+pub struct HistoryCommandHandler;
+
+pub struct HistoryCommandArgs {
+    key: String,
+    scope: Scope,
+}
+
+impl<C: Get<Notes> + Get<Translator>> CommandHandler<C> for HistoryCommandHandler {
+    type Args = HistoryCommandArgs;
+    type Future = Task<CommandHandlerResult>;
+
+    fn name() -> &'static str {
+        "history"
+    }
+
+    fn parse_args(interaction_data: &InteractionData) -> Option<Self::Args> {
+        let key = interaction_data.option("key")?.as_str()?.to_string();
+        let scope = Scope::from_options(interaction_data)?;
+        Some(HistoryCommandArgs { key, scope })
+    }
+
+    fn handle(&self, args: Self::Args, context: &C) -> Self::Future {
+        let notes: Notes = Get::<Notes>::get(context).clone();
+        let translator: Translator = Get::<Translator>::get(context).clone();
+        Box::pin(async move {
+            let note = notes.read(args.scope, &args.key).await?;
+            embed_message(note_history_embed(&args.key, &note, &translator)?, &note)
+        })
+    }
+}
+
+pub struct DiffCommandHandler;
+
+pub struct DiffCommandArgs {
+    key: String,
+    from: u32,
+    /// Current revision when `None`
+    to: Option<u32>,
+    scope: Scope,
+}
+
+impl<C: Get<Notes> + Get<Translator>> CommandHandler<C> for DiffCommandHandler {
+    type Args = DiffCommandArgs;
+    type Future = Task<CommandHandlerResult>;
+
+    fn name() -> &'static str {
+        "diff"
+    }
+
+    fn parse_args(interaction_data: &InteractionData) -> Option<Self::Args> {
+        let key = interaction_data.option("key")?.as_str()?.to_string();
+        let from = revision_option(interaction_data, "from")??;
+        let to = revision_option(interaction_data, "to")?;
+        let scope = Scope::from_options(interaction_data)?;
+        Some(DiffCommandArgs { key, from, to, scope })
+    }
+
+    fn handle(&self, args: Self::Args, context: &C) -> Self::Future {
+        let notes: Notes = Get::<Notes>::get(context).clone();
+        let translator: Translator = Get::<Translator>::get(context).clone();
+        Box::pin(async move {
+            let note = notes.read(args.scope, &args.key).await?;
+            let revision = |number| note.revision(number).ok_or(InteractionError::RevisionNotFound { number });
+            let from = revision(args.from)?;
+            let to = revision(args.to.unwrap_or(note.revision))?;
+            embed_message(note_diff_embed(&args.key, &from, &to, &translator)?, &note)
+        })
+    }
+}
+
+pub struct RestoreCommandHandler;
+
+pub struct RestoreCommandArgs {
+    key: String,
+    revision: u32,
+    scope: Scope,
+}
+
+impl<C: Get<Notes> + Get<Translator>> CommandHandler<C> for RestoreCommandHandler {
+    type Args = RestoreCommandArgs;
+    type Future = Task<CommandHandlerResult>;
+
+    fn name() -> &'static str {
+        "restore"
+    }
+
+    fn parse_args(interaction_data: &InteractionData) -> Option<Self::Args> {
+        let key = interaction_data.option("key")?.as_str()?.to_string();
+        let revision = revision_option(interaction_data, "revision")??;
+        let scope = Scope::from_options(interaction_data)?;
+        Some(RestoreCommandArgs { key, revision, scope })
+    }
+
+    fn handle(&self, args: Self::Args, context: &C) -> Self::Future {
+        let notes: Notes = Get::<Notes>::get(context).clone();
+        let translator: Translator = Get::<Translator>::get(context).clone();
+        Box::pin(async move {
+            let note = notes.restore(args.scope, &args.key, args.revision).await?;
+            embed_message(note_restored_embed(&args.key, args.revision, &translator)?, &note)
+        })
+    }
+}
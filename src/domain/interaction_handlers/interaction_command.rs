@@ -1,8 +1,10 @@
 use std::future::{Future, ready};
 use futures_util::FutureExt;
 use crate::discord::interaction::{Interaction, InteractionType};
+use crate::discord::rest::application_command::ApplicationCommandType;
+use crate::discord::Permissions;
 use crate::domain::command_handlers::{CommandHandler, CommandHandlerResult};
-use crate::domain::interaction_pipeline::{InteractionError, InteractionHandler, InteractionHandlerResult, Task};
+use crate::domain::interaction_pipeline::{InteractionError, InteractionHandler, InteractionHandlerResult, Route, Task};
 
 pub struct InteractionCommandInteractionHandler<T>(T);
 
@@ -17,6 +19,7 @@ impl<CH, C, F> InteractionHandler<C> for InteractionCommandInteractionHandler<CH
             Some(interaction)
                 .filter(|i| i.interaction_type == InteractionType::ApplicationCommand)
                 .and_then(|i| i.data.as_ref())
+                .filter(|d| d.command_type.unwrap_or_default() == ApplicationCommandType::ChatInput)
                 .filter(|d| d.name == <CH as CommandHandler<C>>::name())
                 .map(|i| <CH as CommandHandler<C>>::parse_args(i).ok_or(InteractionError::InvalidCommand));
         match args {
@@ -25,6 +28,18 @@ impl<CH, C, F> InteractionHandler<C> for InteractionCommandInteractionHandler<CH
             None => Box::pin(ready(None)),
         }
     }
+
+    fn route(&self) -> Route {
+        Route::command(<CH as CommandHandler<C>>::name())
+    }
+
+    fn required_permissions(&self) -> Permissions {
+        <CH as CommandHandler<C>>::required_permissions()
+    }
+
+    fn required_bot_permissions(&self) -> Permissions {
+        <CH as CommandHandler<C>>::required_bot_permissions()
+    }
 }
 
 impl<T> From<T> for InteractionCommandInteractionHandler<T> {
@@ -1,15 +1,82 @@
+use crate::discord::Snowflake;
 use crate::domain::command_handlers::{
-    CommandHandler, CommandHandlerResult,
+    CommandHandler, CommandHandlerResult, UserCommandHandler, UserCommandTarget,
 };
-use crate::domain::interaction_pipeline::Task;
+use crate::domain::component_handlers::{ComponentHandler, ComponentHandlerResult};
+use crate::domain::interaction_pipeline::{InteractionError, Task};
+use crate::domain::paginator::{parse_page, Pages, PAGE_LIMIT};
+use crate::discord::interaction::embed::{truncate, TITLE_LIMIT};
 use crate::discord::interaction::{InteractionCallback, InteractionCallbackMessage, InteractionData};
 use crate::domain::bot::{Get};
-use crate::domain::store::Storage;
+use crate::domain::i18n::Translator;
+use crate::domain::namespace::{Notes, Scope};
+use crate::domain::note::Visibility;
+use super::notes::{note_list_embed, notes_by_user_embed};
+
+const LS_PAGE_ID: &str = "ls";
+const PRIVATE: &str = "private";
+/// Marks the author whose notes are listed, like `by42`
+const AUTHOR_ARG_PREFIX: &str = "by";
+
+/// Notes shown by one `ls` or "Notes by user" message, carried by its prev/next buttons
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Listing {
+    scope: Scope,
+    /// Only the user who ran `/ls` may page, others would see their own notes
+    owner: Snowflake,
+    /// Private keys are only listed in ephemeral messages
+    private: bool,
+    /// Only notes written by this user are listed
+    author: Option<Snowflake>,
+}
+
+impl Listing {
+    /// `[global:]<owner>[:private][:by<author>]`, the paginator appends the page
+    fn custom_id_prefix(&self) -> String {
+        let mut args = vec![self.owner.to_string()];
+        if self.private {
+            args.push(PRIVATE.to_owned());
+        }
+        if let Some(author) = self.author {
+            args.push(format!("{}{}", AUTHOR_ARG_PREFIX, author));
+        }
+        self.scope.custom_id(LS_PAGE_ID, &args.join(":"))
+    }
+
+    fn parse(custom_id_args: &str) -> Option<(Self, usize)> {
+        let (scope, args) = Scope::split_custom_id_args(custom_id_args);
+        let (args, page) = args.rsplit_once(':')?;
+        let mut args = args.split(':');
+        let owner = args.next()?.parse().ok()?;
+        let mut listing = Listing { scope, owner, private: false, author: None };
+        for arg in args {
+            match arg.strip_prefix(AUTHOR_ARG_PREFIX) {
+                Some(author) => listing.author = Some(author.parse().ok()?),
+                None if arg == PRIVATE => listing.private = true,
+                None => return None,
+            }
+        }
+        Some((listing, parse_page(page)?))
+    }
+
+    /// Keys to list, private ones only if the message is ephemeral
+    async fn keys(&self, notes: &Notes) -> Result<Vec<String>, InteractionError> {
+        let notes = match self.author {
+            Some(author) => notes.authored_by(self.scope, author).await?,
+            None => notes.list(self.scope).await?,
+        };
+        Ok(notes
+            .into_iter()
+            .filter(|(_, visibility)| self.private || *visibility != Visibility::Private)
+            .map(|(key, _)| key)
+            .collect())
+    }
+}
 
 pub struct LsCommandHandler;
 
-impl<C: Get<Storage>> CommandHandler<C> for LsCommandHandler {
-    type Args = ();
+impl<C: Get<Notes> + Get<Translator>> CommandHandler<C> for LsCommandHandler {
+    type Args = Scope;
     type Future = Task<CommandHandlerResult>;
 
     fn name() -> &'static str {
@@ -17,26 +84,208 @@ impl<C: Get<Storage>> CommandHandler<C> for LsCommandHandler {
     }
 
     fn parse_args(interaction_data: &InteractionData) -> Option<Self::Args> {
-        match interaction_data.options.as_deref() {
-            Some([]) | None => Some(()),
-            _ => None,
-        }
+        Scope::from_options(interaction_data)
     }
 
-    fn handle(&self, _: Self::Args, context: &C) -> Self::Future {
-        let store: Storage = context.get().clone();
+    fn handle(&self, scope: Self::Args, context: &C) -> Self::Future {
+        let notes: Notes = Get::<Notes>::get(context).clone();
+        let translator: Translator = Get::<Translator>::get(context).clone();
         Box::pin(async move {
-            let entries = store.list().await?;
-            let message_text = entries
+            let owner = notes.requester_id().ok_or(InteractionError::InvalidCommand)?;
+            let private = notes
+                .list(scope)
+                .await?
                 .iter()
-                .map(|s| format!("| {s}"))
-                .collect::<Vec<String>>()
-                .join("\n");
-            let message = InteractionCallbackMessage {
-                content: Some(message_text),
-            };
+                .any(|(_, visibility)| *visibility == Visibility::Private);
+            let listing = Listing { scope, owner, private, author: None };
+            let message = list_page(&notes, listing, 0, &translator).await?;
+            let message = if private { message.ephemeral() } else { message };
             let callback = InteractionCallback::channel_message_with_source(message);
-            Ok(callback)
+            Ok(callback.into())
+        })
+    }
+}
+
+/// Prev/next buttons of the `ls` output, `custom_id_args` is the listing and the page to show
+pub struct LsPageComponentHandler;
+
+impl<C: Get<Notes> + Get<Translator>> ComponentHandler<C> for LsPageComponentHandler {
+    type Args = (Listing, usize);
+    type Future = Task<ComponentHandlerResult>;
+
+    fn custom_id_prefix() -> &'static str {
+        LS_PAGE_ID
+    }
+
+    fn parse_args(custom_id_args: &str, _: &InteractionData) -> Option<Self::Args> {
+        Listing::parse(custom_id_args)
+    }
+
+    fn handle(&self, (listing, page): Self::Args, context: &C) -> Self::Future {
+        let notes: Notes = Get::<Notes>::get(context).clone();
+        let translator: Translator = Get::<Translator>::get(context).clone();
+        Box::pin(async move {
+            if notes.requester_id() != Some(listing.owner) {
+                return Err(InteractionError::NotInvoker);
+            }
+            let message = list_page(&notes, listing, page, &translator).await?;
+            Ok(InteractionCallback::update_message(message).into())
+        })
+    }
+}
+
+/// "Notes by user" in the context menu of users: the local notes the user wrote, as far as the
+/// requester may read them, paged like `/ls`. Always ephemeral, it may list private notes.
+pub struct NotesByUserCommandHandler;
+
+impl<C: Get<Notes> + Get<Translator>> UserCommandHandler<C> for NotesByUserCommandHandler {
+    type Future = Task<CommandHandlerResult>;
+
+    fn name() -> &'static str {
+        "Notes by user"
+    }
+
+    fn handle(&self, target: UserCommandTarget, context: &C) -> Self::Future {
+        let notes: Notes = Get::<Notes>::get(context).clone();
+        let translator: Translator = Get::<Translator>::get(context).clone();
+        Box::pin(async move {
+            let owner = notes.requester_id().ok_or(InteractionError::InvalidCommand)?;
+            let listing = Listing { scope: Scope::Local, owner, private: true, author: Some(target.user.id) };
+            let message = list_page(&notes, listing, 0, &translator).await?;
+            Ok(InteractionCallback::channel_message_with_source(message.ephemeral()).into())
         })
     }
 }
+
+async fn list_page(
+    notes: &Notes,
+    listing: Listing,
+    page: usize,
+    translator: &Translator,
+) -> Result<InteractionCallbackMessage, InteractionError> {
+    let keys = listing.keys(notes).await?;
+    let lines = keys.iter().map(|key| format!("• {}", truncate(key, TITLE_LIMIT)));
+    let pages = Pages::from_lines(lines, PAGE_LIMIT);
+    let page = pages.page(page);
+    let embed = match listing.author {
+        Some(author) => notes_by_user_embed(author, &page, keys.len(), translator)?,
+        None => note_list_embed(&page, keys.len(), translator)?,
+    };
+    Ok(InteractionCallbackMessage {
+        embeds: Some(Box::new([embed])),
+        // An empty list rather than none, so stale buttons are removed when a page is updated
+        components: Some(
+            page.buttons(&listing.custom_id_prefix(), translator)
+                .map(|row| Box::new([row.into()]) as Box<[_]>)
+                .unwrap_or_default(),
+        ),
+        ..Default::default()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Listing, LsCommandHandler, LsPageComponentHandler, NotesByUserCommandHandler};
+    use crate::discord::interaction::message::MessageFlags;
+    use crate::discord::interaction::InteractionData;
+    use crate::domain::bot::Get;
+    use crate::domain::command_handlers::{CommandHandler, UserCommandHandler, UserCommandTarget};
+    use crate::domain::component_handlers::ComponentHandler;
+    use crate::domain::i18n::Translator;
+    use crate::domain::interaction_pipeline::{InteractionError, InteractionResponse};
+    use crate::domain::namespace::{Namespace, Notes, Requester, Scope};
+    use crate::domain::note::Visibility;
+    use crate::domain::store::Storage;
+    use serde_json::json;
+
+    struct TestContext(Notes, Translator);
+
+    impl Get<Notes> for TestContext {
+        fn get(&self) -> &Notes {
+            &self.0
+        }
+    }
+
+    impl Get<Translator> for TestContext {
+        fn get(&self) -> &Translator {
+            &self.1
+        }
+    }
+
+    fn context(storage: &Storage, user_id: &str) -> TestContext {
+        let requester = Requester { user_id: user_id.parse().unwrap(), admin: false, moderator: false };
+        let guild = Some(Namespace::Guild("1".parse().unwrap()));
+        TestContext(Notes::new(storage.clone(), guild, Some(requester)), Translator::default())
+    }
+
+    #[test]
+    fn listing_round_trips_through_custom_id() {
+        let listing = Listing { scope: Scope::Global, owner: "5".parse().unwrap(), private: true, author: None };
+        assert_eq!(listing.custom_id_prefix(), "ls:global:5:private");
+        assert_eq!(Listing::parse("global:5:private:2"), Some((listing, 2)));
+        let public = Listing { scope: Scope::Local, owner: "5".parse().unwrap(), private: false, author: None };
+        assert_eq!(Listing::parse("5:0"), Some((public, 0)));
+        assert!(Listing::parse("5:public:0").is_none());
+        let by_author = Listing { author: Some("7".parse().unwrap()), ..listing };
+        assert_eq!(by_author.custom_id_prefix(), "ls:global:5:private:by7");
+        assert_eq!(Listing::parse("global:5:private:by7:1"), Some((by_author, 1)));
+    }
+
+    #[actix_rt::test]
+    async fn private_keys_are_listed_to_their_author_only() {
+        let storage = Storage::memory();
+        let author = context(&storage, "2");
+        author.0.save(Scope::Local, "diary", String::from("text"), Some(Visibility::Private), None).await.unwrap();
+        let data: InteractionData = serde_json::from_value(json!({"id": "1", "name": "ls"})).unwrap();
+
+        let args = <LsCommandHandler as CommandHandler<TestContext>>::parse_args(&data).unwrap();
+        let Ok(InteractionResponse::Callback(callback)) = LsCommandHandler.handle(args, &author).await else {
+            panic!("ls failed");
+        };
+        let message = callback.into_message().unwrap();
+        assert!(message.flags.contains(MessageFlags::EPHEMERAL));
+        assert_eq!(message.components.as_deref().map(<[_]>::len), Some(0));
+
+        let args = <LsPageComponentHandler as ComponentHandler<TestContext>>::parse_args("2:private:0", &data).unwrap();
+        let denied = LsPageComponentHandler.handle(args, &context(&storage, "3")).await;
+        assert!(matches!(denied, Err(InteractionError::NotInvoker)));
+    }
+
+    #[actix_rt::test]
+    async fn notes_by_user_are_paged_for_the_invoker() {
+        let storage = Storage::memory();
+        let author = context(&storage, "2");
+        for i in 0..40 {
+            let key = format!("{:02} {}", i, "x".repeat(90));
+            author.0.save(Scope::Local, &key, String::from("text"), None, None).await.unwrap();
+        }
+        context(&storage, "3").0.save(Scope::Local, "other", String::from("text"), None, None).await.unwrap();
+        let target = UserCommandTarget {
+            user: serde_json::from_value(json!({"id": "2", "username": "author"})).unwrap(),
+            member: None,
+        };
+
+        let invoker = context(&storage, "4");
+        let Ok(InteractionResponse::Callback(callback)) = NotesByUserCommandHandler.handle(target, &invoker).await else {
+            panic!("notes by user failed");
+        };
+        let message = callback.into_message().unwrap();
+        assert!(message.flags.contains(MessageFlags::EPHEMERAL));
+        let embed = serde_json::to_value(&message.embeds.unwrap()[0]).unwrap();
+        assert!(embed["description"].as_str().unwrap().starts_with("Notes by <@2>\n\n• 00"));
+        let buttons = serde_json::to_value(&message.components.unwrap()[0]).unwrap();
+        assert_eq!(buttons["components"][1]["custom_id"], json!("ls:4:private:by2:1"));
+
+        let data: InteractionData = serde_json::from_value(json!({"id": "1", "name": "ls"})).unwrap();
+        let args = <LsPageComponentHandler as ComponentHandler<TestContext>>::parse_args("4:private:by2:1", &data).unwrap();
+        let Ok(InteractionResponse::Callback(callback)) = LsPageComponentHandler.handle(args, &invoker).await else {
+            panic!("paging failed");
+        };
+        let embed = serde_json::to_value(&callback.into_message().unwrap().embeds.unwrap()[0]).unwrap();
+        let description = embed["description"].as_str().unwrap();
+        assert!(description.starts_with("Notes by <@2>\n\n• "));
+        assert!(!description.contains("other"));
+        let denied = LsPageComponentHandler.handle(args, &author).await;
+        assert!(matches!(denied, Err(InteractionError::NotInvoker)));
+    }
+}
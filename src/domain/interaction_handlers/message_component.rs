@@ -0,0 +1,42 @@
+use std::future::{Future, ready};
+use futures_util::FutureExt;
+use crate::discord::interaction::{Interaction, InteractionType};
+use crate::domain::component_handlers::{split_custom_id, ComponentHandler, ComponentHandlerResult};
+use crate::domain::interaction_pipeline::{InteractionError, InteractionHandler, InteractionHandlerResult, Route, Task};
+
+pub struct MessageComponentInteractionHandler<T>(T);
+
+impl<CH, C, F> InteractionHandler<C> for MessageComponentInteractionHandler<CH>
+    where CH: ComponentHandler<C, Future=F>,
+          F: Future<Output=ComponentHandlerResult> + 'static
+{
+    type Future = Task<InteractionHandlerResult>;
+
+    fn handle(&self, interaction: &Interaction, context: &C) -> Self::Future {
+        let args: Option<Result<<CH as ComponentHandler<C>>::Args, InteractionError>> =
+            Some(interaction)
+                .filter(|i| i.interaction_type == InteractionType::MessageComponent)
+                .and_then(|i| i.data.as_ref())
+                .and_then(|d| d.custom_id.as_deref().map(|id| (split_custom_id(id), d)))
+                .filter(|((prefix, _), _)| *prefix == <CH as ComponentHandler<C>>::custom_id_prefix())
+                .map(|((_, custom_id_args), d)| {
+                    <CH as ComponentHandler<C>>::parse_args(custom_id_args, d)
+                        .ok_or(InteractionError::InvalidCommand)
+                });
+        match args {
+            Some(Ok(args)) => Box::pin(self.0.handle(args, context).map(Some)),
+            Some(Err(e)) => Box::pin(ready(Some(Err(e)))),
+            None => Box::pin(ready(None)),
+        }
+    }
+
+    fn route(&self) -> Route {
+        Route::Component(<CH as ComponentHandler<C>>::custom_id_prefix().to_owned())
+    }
+}
+
+impl<T> From<T> for MessageComponentInteractionHandler<T> {
+    fn from(e: T) -> Self {
+        MessageComponentInteractionHandler(e)
+    }
+}
@@ -1,14 +1,29 @@
+mod del;
 mod echo;
+mod edit;
 mod get;
+mod history;
 mod ls;
 mod ping;
+mod rename;
 mod set;
 mod interaction_command;
+mod context_menu;
+mod message_component;
+mod modal_submit;
+mod notes;
 
 
+pub use del::{DelCommandHandler, DelConfirmComponentHandler};
 pub use echo::EchoCommandHandler;
+pub use edit::EditCommandHandler;
 pub use get::GetCommandHandler;
-pub use ls::LsCommandHandler;
+pub use history::{DiffCommandHandler, HistoryCommandHandler, RestoreCommandHandler};
+pub use ls::{LsCommandHandler, LsPageComponentHandler, NotesByUserCommandHandler};
 pub use ping::PingInteractionHandler;
-pub use set::SetCommandHandler;
-pub use interaction_command::InteractionCommandInteractionHandler;
\ No newline at end of file
+pub use rename::RenameCommandHandler;
+pub use set::{SaveAsNoteCommandHandler, SetCommandHandler, SetModalSubmitHandler};
+pub use interaction_command::InteractionCommandInteractionHandler;
+pub use context_menu::{MessageCommandInteractionHandler, UserCommandInteractionHandler};
+pub use message_component::MessageComponentInteractionHandler;
+pub use modal_submit::ModalSubmitInteractionHandler;
@@ -0,0 +1,44 @@
+use std::future::{Future, ready};
+use futures_util::FutureExt;
+use crate::discord::interaction::{Interaction, InteractionType};
+use crate::discord::interaction::modal::ModalSubmitValues;
+use crate::domain::component_handlers::{split_custom_id, ComponentHandlerResult, ModalSubmitHandler};
+use crate::domain::interaction_pipeline::{InteractionError, InteractionHandler, InteractionHandlerResult, Route, Task};
+
+pub struct ModalSubmitInteractionHandler<T>(T);
+
+impl<MH, C, F> InteractionHandler<C> for ModalSubmitInteractionHandler<MH>
+    where MH: ModalSubmitHandler<C, Future=F>,
+          F: Future<Output=ComponentHandlerResult> + 'static
+{
+    type Future = Task<InteractionHandlerResult>;
+
+    fn handle(&self, interaction: &Interaction, context: &C) -> Self::Future {
+        let args: Option<Result<<MH as ModalSubmitHandler<C>>::Args, InteractionError>> =
+            Some(interaction)
+                .filter(|i| i.interaction_type == InteractionType::ModalSubmit)
+                .and_then(|i| i.data.as_ref())
+                .and_then(|d| d.custom_id.as_deref().map(|id| (split_custom_id(id), d)))
+                .filter(|((prefix, _), _)| *prefix == <MH as ModalSubmitHandler<C>>::custom_id_prefix())
+                .map(|((_, custom_id_args), d)| {
+                    let values = ModalSubmitValues::from_data(d);
+                    <MH as ModalSubmitHandler<C>>::parse_args(custom_id_args, &values)
+                        .ok_or(InteractionError::InvalidCommand)
+                });
+        match args {
+            Some(Ok(args)) => Box::pin(self.0.handle(args, context).map(Some)),
+            Some(Err(e)) => Box::pin(ready(Some(Err(e)))),
+            None => Box::pin(ready(None)),
+        }
+    }
+
+    fn route(&self) -> Route {
+        Route::ModalSubmit(<MH as ModalSubmitHandler<C>>::custom_id_prefix().to_owned())
+    }
+}
+
+impl<T> From<T> for ModalSubmitInteractionHandler<T> {
+    fn from(e: T) -> Self {
+        ModalSubmitInteractionHandler(e)
+    }
+}
@@ -0,0 +1,147 @@
+use crate::discord::interaction::embed::{truncate, Embed, EmbedBuilder, EmbedError, DESCRIPTION_LIMIT, TITLE_LIMIT};
+use crate::discord::Snowflake;
+use crate::domain::diff::line_diff;
+use crate::domain::i18n::{Arg, Translator};
+use crate::domain::note::{Note, Revision};
+use crate::domain::paginator::Page;
+
+const NOTE_COLOR: u32 = 0x5865F2;
+
+pub fn note_embed(key: &str, value: &str) -> Result<Embed, EmbedError> {
+    Embed::build()
+        .with_title(&truncate(key, TITLE_LIMIT))
+        .with_description(&truncate(value, DESCRIPTION_LIMIT))
+        .with_color(NOTE_COLOR)
+        .finish()
+}
+
+pub fn note_saved_embed(key: &str, translator: &Translator) -> Result<Embed, EmbedError> {
+    Embed::build()
+        .with_title(&translator.text("note-saved"))
+        .with_description(&format!("`{}`", truncate(key, DESCRIPTION_LIMIT - 2)))
+        .with_color(NOTE_COLOR)
+        .finish()
+}
+
+pub fn note_renamed_embed(from: &str, to: &str, translator: &Translator) -> Result<Embed, EmbedError> {
+    let limit = DESCRIPTION_LIMIT / 2 - 2;
+    Embed::build()
+        .with_title(&translator.text("note-renamed"))
+        .with_description(&format!("`{}` → `{}`", truncate(from, limit), truncate(to, limit)))
+        .with_color(NOTE_COLOR)
+        .finish()
+}
+
+pub fn note_restored_embed(key: &str, number: u32, translator: &Translator) -> Result<Embed, EmbedError> {
+    Embed::build()
+        .with_title(&translator.message("note-restored", &[("revision", Arg::from(u64::from(number)))]))
+        .with_description(&format!("`{}`", truncate(key, DESCRIPTION_LIMIT - 2)))
+        .with_color(NOTE_COLOR)
+        .finish()
+}
+
+/// Kept revisions of `note`, newest first
+pub fn note_history_embed(key: &str, note: &Note, translator: &Translator) -> Result<Embed, EmbedError> {
+    let lines: Vec<String> = note
+        .revisions()
+        .map(|revision| {
+            let editor = match revision.editor_id {
+                Some(id) => format!("<@{}>", id),
+                None => translator.text("history-unknown-editor"),
+            };
+            let args = [
+                ("revision", Arg::from(u64::from(revision.number))),
+                ("editor", Arg::from(editor)),
+                ("time", Arg::from(format!("<t:{}:R>", revision.updated_at.timestamp()))),
+            ];
+            translator.message("history-revision", &args)
+        })
+        .collect();
+    Embed::build()
+        .with_title(&truncate(
+            &translator.message("history-title", &[("key", Arg::from(key))]),
+            TITLE_LIMIT,
+        ))
+        .with_description(&truncate(&lines.join("\n"), DESCRIPTION_LIMIT))
+        .with_color(NOTE_COLOR)
+        .finish()
+}
+
+/// Changes from revision `from` to revision `to`
+pub fn note_diff_embed(key: &str, from: &Revision, to: &Revision, translator: &Translator) -> Result<Embed, EmbedError> {
+    // a zero width space between backticks keeps values from closing the code block
+    let diff = line_diff(&from.value, &to.value).replace("``", "`\u{200B}`");
+    // room for the code block around the diff
+    let diff = truncate(&diff, DESCRIPTION_LIMIT - 12);
+    let args = [
+        ("key", Arg::from(key)),
+        ("from", Arg::from(u64::from(from.number))),
+        ("to", Arg::from(u64::from(to.number))),
+    ];
+    Embed::build()
+        .with_title(&truncate(&translator.message("diff-title", &args), TITLE_LIMIT))
+        .with_description(&format!("```diff\n{}\n```", diff))
+        .with_color(NOTE_COLOR)
+        .finish()
+}
+
+/// One page of the note list, see `Pages::from_lines`
+pub fn note_list_embed(page: &Page, total: usize, translator: &Translator) -> Result<Embed, EmbedError> {
+    note_list(page, total, None, translator).finish()
+}
+
+/// One page of the notes written by `author_id`, who is mentioned above the list on every page
+pub fn notes_by_user_embed(
+    author_id: Snowflake,
+    page: &Page,
+    total: usize,
+    translator: &Translator,
+) -> Result<Embed, EmbedError> {
+    let heading = translator.message("notes-by-user", &[("user", Arg::from(format!("<@{}>", author_id)))]);
+    note_list(page, total, Some(&heading), translator).finish()
+}
+
+fn note_list(page: &Page, total: usize, heading: Option<&str>, translator: &Translator) -> EmbedBuilder {
+    let list = if total == 0 {
+        translator.text("notes-empty")
+    } else {
+        page.content.to_owned()
+    };
+    let description = match heading {
+        Some(heading) => format!("{}\n\n{}", heading, list),
+        None => list,
+    };
+    let total = translator.message("notes-total", &[("total", Arg::from(total))]);
+    let footer = if page.count > 1 {
+        format!("{} · {}", page.label(translator), total)
+    } else {
+        total
+    };
+    Embed::build()
+        .with_title(&translator.text("notes-title"))
+        .with_description(&description)
+        .with_footer(&footer, None)
+        .with_color(NOTE_COLOR)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::note_diff_embed;
+    use crate::domain::i18n::Translator;
+    use crate::domain::note::Revision;
+    use chrono::Utc;
+
+    fn revision(number: u32, value: &str) -> Revision {
+        Revision { number, value: value.to_owned(), editor_id: None, updated_at: Utc::now() }
+    }
+
+    #[test]
+    fn values_can_not_close_the_diff_block() {
+        let from = revision(1, "plain");
+        let to = revision(2, "`````\n@everyone");
+        let embed = note_diff_embed("todo", &from, &to, &Translator::default()).unwrap();
+        let description = embed.description.unwrap();
+        assert_eq!(description.matches("```").count(), 2);
+        assert!(description.starts_with("```diff\n") && description.ends_with("\n```"));
+    }
+}
@@ -1,4 +1,4 @@
-use crate::domain::interaction_pipeline::{NoContextInteractionHandler, InteractionHandlerResult, Task};
+use crate::domain::interaction_pipeline::{NoContextInteractionHandler, InteractionHandlerResult, Route, Task};
 use std::future::ready;
 use crate::discord::interaction::{Interaction, InteractionCallback, InteractionType};
 
@@ -9,9 +9,13 @@ impl NoContextInteractionHandler for PingInteractionHandler {
 
     fn handle(&self, interaction: &Interaction) -> Self::Future {
         if interaction.interaction_type == InteractionType::Ping {
-            Box::pin(ready(Some(Ok(InteractionCallback::pong()))))
+            Box::pin(ready(Some(Ok(InteractionCallback::pong().into()))))
         } else {
             Box::pin(ready(None))
         }
     }
+
+    fn route(&self) -> Route {
+        Route::Ping
+    }
 }
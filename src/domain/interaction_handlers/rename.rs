@@ -0,0 +1,46 @@
+use crate::discord::interaction::{InteractionCallback, InteractionCallbackMessage, InteractionData};
+use crate::domain::bot::Get;
+use crate::domain::command_handlers::{CommandHandler, CommandHandlerResult};
+use crate::domain::i18n::Translator;
+use crate::domain::interaction_pipeline::Task;
+use crate::domain::namespace::{Notes, Scope};
+use super::notes::note_renamed_embed;
+use super::set::note_key;
+
+pub struct RenameCommandHandler;
+
+pub struct RenameCommandArgs {
+    key: String,
+    new_key: String,
+    scope: Scope,
+}
+
+impl<C: Get<Notes> + Get<Translator>> CommandHandler<C> for RenameCommandHandler {
+    type Args = RenameCommandArgs;
+    type Future = Task<CommandHandlerResult>;
+
+    fn name() -> &'static str {
+        "rename"
+    }
+
+    fn parse_args(interaction_data: &InteractionData) -> Option<Self::Args> {
+        let key = interaction_data.option("key")?.as_str()?.to_string();
+        let new_key = interaction_data.option("new_key")?.as_str()?.to_string();
+        let scope = Scope::from_options(interaction_data)?;
+        Some(RenameCommandArgs { key, new_key, scope })
+    }
+
+    fn handle(&self, args: Self::Args, context: &C) -> Self::Future {
+        let notes: Notes = Get::<Notes>::get(context).clone();
+        let translator: Translator = Get::<Translator>::get(context).clone();
+        Box::pin(async move {
+            let new_key = note_key(&args.new_key)?;
+            notes.rename(args.scope, &args.key, &new_key).await?;
+            let message = InteractionCallbackMessage {
+                embeds: Some(Box::new([note_renamed_embed(&args.key, &new_key, &translator)?])),
+                ..Default::default()
+            };
+            Ok(InteractionCallback::channel_message_with_source(message).into())
+        })
+    }
+}
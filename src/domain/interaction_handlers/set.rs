@@ -1,22 +1,114 @@
-use crate::discord::rest::application_command::ApplicationCommandOptionValue::Str;
-use crate::discord::rest::application_command::ApplicationCommandType;
+use crate::discord::rest::DiscordBotApiClient;
 
 use crate::domain::command_handlers::{
-    CommandHandler, CommandHandlerResult,
+    CommandHandler, CommandHandlerResult, MessageCommandHandler,
 };
-use crate::domain::interaction_pipeline::Task;
-use crate::discord::interaction::{ApplicationCommandInteractionDataOption, InteractionCallback, InteractionCallbackMessage, InteractionData};
+use crate::domain::component_handlers::{ComponentHandlerResult, ModalSubmitHandler};
+use crate::domain::interaction_pipeline::{InteractionError, Task};
+use crate::discord::interaction::{InteractionCallback, InteractionCallbackMessage, InteractionData};
+use crate::discord::interaction::embed::truncate;
+use crate::discord::interaction::message::{Attachment, Message};
+use crate::discord::interaction::modal::{Modal, ModalSubmitValues, TextInput};
 use crate::domain::bot::{Get};
-use crate::domain::store::Storage;
+use crate::domain::i18n::Translator;
+use crate::domain::namespace::{Notes, Scope};
+use crate::domain::note::{Ttl, Visibility};
+use super::notes::note_saved_embed;
+
+const SET_MODAL_ID: &str = "set";
+const KEY_INPUT_ID: &str = "key";
+const VALUE_INPUT_ID: &str = "value";
+const KEY_MAX_LENGTH: u16 = 100;
+pub(super) const VALUE_MAX_LENGTH: u16 = 4000;
+const VISIBILITY_OPTION: &str = "visibility";
+const TTL_OPTION: &str = "ttl";
+/// Marks the TTL in minutes among the custom id args of the modal
+const TTL_ARG_PREFIX: &str = "ttl";
+/// Marks the digest of the key being edited among the custom id args of the modal
+const EDIT_ARG_PREFIX: &str = "edit";
+/// Largest file accepted as the value of a note
+const MAX_FILE_SIZE: u64 = 1024 * 1024;
 
 pub struct SetCommandHandler;
 
+/// Where a note is saved, carried through the modal in its custom id
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct NoteTarget {
+    scope: Scope,
+    /// Kept as it is when updating a note without the option
+    visibility: Option<Visibility>,
+    /// Kept as it is when updating a note without the option
+    ttl: Option<Ttl>,
+    /// Digest of the key opened with /edit, custom ids are too short for the key itself
+    edited_key: Option<u64>,
+}
+
+impl From<Scope> for NoteTarget {
+    fn from(scope: Scope) -> Self {
+        NoteTarget { scope, ..Default::default() }
+    }
+}
+
+impl NoteTarget {
+    /// Target of the modal opened by /edit, which must not save the note under another key
+    pub(super) fn editing(scope: Scope, key: &str) -> Self {
+        NoteTarget { scope, edited_key: Some(key_digest(key)), ..Default::default() }
+    }
+
+    /// Visibility code, TTL like `ttl30` and the edited key like `edit1f` follow the scope, separated by colons
+    fn custom_id(&self, prefix: &str) -> String {
+        let visibility = self.visibility.map(|v| v.code().to_owned());
+        let ttl = self.ttl.map(|ttl| format!("{}{}", TTL_ARG_PREFIX, ttl.as_minutes()));
+        let edited_key = self.edited_key.map(|digest| format!("{}{:x}", EDIT_ARG_PREFIX, digest));
+        let args: Vec<String> = visibility.into_iter().chain(ttl).chain(edited_key).collect();
+        self.scope.custom_id(prefix, &args.join(":"))
+    }
+
+    fn parse(custom_id_args: &str) -> Option<Self> {
+        let (scope, args) = Scope::split_custom_id_args(custom_id_args);
+        let mut target = NoteTarget::from(scope);
+        for arg in args.split(':').filter(|arg| !arg.is_empty()) {
+            if let Some(digest) = arg.strip_prefix(EDIT_ARG_PREFIX) {
+                target.edited_key = Some(u64::from_str_radix(digest, 16).ok()?);
+            } else if let Some(minutes) = arg.strip_prefix(TTL_ARG_PREFIX) {
+                target.ttl = Some(Ttl::minutes(minutes.parse().ok()?));
+            } else {
+                target.visibility = Some(arg.parse().ok()?);
+            }
+        }
+        Some(target)
+    }
+
+    /// Keys of edited notes can only be changed with /rename
+    fn check_key(&self, key: &str) -> Result<(), InteractionError> {
+        match self.edited_key {
+            Some(digest) if digest != key_digest(key) => Err(InteractionError::KeyChanged),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// FNV-1a of the trimmed key, stable across restarts unlike the std hasher
+fn key_digest(key: &str) -> u64 {
+    key.trim()
+        .bytes()
+        .fold(0xcbf2_9ce4_8422_2325, |hash, byte| (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3))
+}
+
 pub struct SetCommandArgs {
-    key: String,
-    value: String,
+    target: NoteTarget,
+    note: NoteArgs,
+}
+
+pub enum NoteArgs {
+    Note { key: String, value: String },
+    /// `/set` without key or value opens a modal, prefilled with what was given
+    OpenModal { key: Option<String>, value: Option<String> },
+    /// Text of the uploaded file becomes the value, its name is the default key
+    File { key: Option<String>, attachment: Attachment },
 }
 
-impl<C: Get<Storage>> CommandHandler<C> for SetCommandHandler {
+impl<C: Get<Notes> + Get<DiscordBotApiClient> + Get<Translator>> CommandHandler<C> for SetCommandHandler {
     type Args = SetCommandArgs;
     type Future = Task<CommandHandlerResult>;
 
@@ -25,34 +117,297 @@ impl<C: Get<Storage>> CommandHandler<C> for SetCommandHandler {
     }
 
     fn parse_args(interaction_data: &InteractionData) -> Option<Self::Args> {
-        interaction_data.options.as_ref()
-            .and_then(|o| match o.as_ref() {
-                [ApplicationCommandInteractionDataOption {
-                    name: key_name,
-                    application_command_option_type: ApplicationCommandType::String,
-                    value: Str(key),
-                }, ApplicationCommandInteractionDataOption {
-                    name: value_name,
-                    application_command_option_type: ApplicationCommandType::String,
-                    value: Str(value),
-                }] if key_name == "key" && value_name == "value" => Some(SetCommandArgs {
-                    key: key.to_string(),
-                    value: value.to_string(),
-                }),
-                _ => None,
-            })
-    }
-
-    fn handle(&self, args: Self::Args, context: &C) -> Self::Future {
-        let store: Storage = context.get().clone();
+        let key = interaction_data.option("key").and_then(|v| v.as_str()).map(String::from);
+        let value = interaction_data.option("value").and_then(|v| v.as_str()).map(String::from);
+        let note = match (key, value, interaction_data.option("file")) {
+            (key, None, Some(_)) => {
+                let attachment = interaction_data.attachment("file")?.clone();
+                NoteArgs::File { key, attachment }
+            }
+            (Some(key), Some(value), None) => NoteArgs::Note { key, value },
+            (key, value, None) => NoteArgs::OpenModal { key, value },
+            _ => return None,
+        };
+        let scope = Scope::from_options(interaction_data)?;
+        let visibility = match interaction_data.option(VISIBILITY_OPTION) {
+            Some(value) => Some(value.as_str()?.parse().ok()?),
+            None => None,
+        };
+        let ttl = match interaction_data.option(TTL_OPTION) {
+            Some(value) => Some(Ttl::minutes(value.as_i64()?.try_into().ok()?)),
+            None => None,
+        };
+        let target = NoteTarget { scope, visibility, ttl, edited_key: None };
+        Some(SetCommandArgs { target, note })
+    }
+
+    fn handle(&self, SetCommandArgs { target, note }: Self::Args, context: &C) -> Self::Future {
+        let notes: Notes = Get::<Notes>::get(context).clone();
+        let client: DiscordBotApiClient = Get::<DiscordBotApiClient>::get(context).clone();
+        let translator: Translator = Get::<Translator>::get(context).clone();
+        Box::pin(async move {
+            match note {
+                NoteArgs::Note { key, value } => set_note(&notes, target, key, value, &translator).await,
+                NoteArgs::File { key, attachment } => {
+                    let value = read_text_file(&client, &attachment).await?;
+                    let key = key.unwrap_or(attachment.filename);
+                    set_note(&notes, target, key, value, &translator).await
+                }
+                NoteArgs::OpenModal { key, value } => {
+                    let modal = set_modal(target, key.as_deref(), value.as_deref(), &translator);
+                    Ok(InteractionCallback::modal(modal).into())
+                }
+            }
+        })
+    }
+}
+
+pub(super) fn set_modal(target: NoteTarget, key: Option<&str>, value: Option<&str>, translator: &Translator) -> Modal {
+    let key_input = TextInput::short(KEY_INPUT_ID, &translator.text("set-modal-key"))
+        .with_length(1, KEY_MAX_LENGTH)
+        .required();
+    let key_input = match key {
+        Some(key) => key_input.with_value(key),
+        None => key_input.with_placeholder(&translator.text("set-modal-key-placeholder")),
+    };
+    let value_input = TextInput::paragraph(VALUE_INPUT_ID, &translator.text("set-modal-value"))
+        .with_length(1, VALUE_MAX_LENGTH)
+        .required();
+    let value_input = match value {
+        Some(value) => value_input.with_value(&truncate(value, VALUE_MAX_LENGTH.into())),
+        None => value_input.with_placeholder(&translator.text("set-modal-value-placeholder")),
+    };
+    Modal::build(&target.custom_id(SET_MODAL_ID), &translator.text("set-modal-title"))
+        .with_text_input(key_input)
+        .with_text_input(value_input)
+        .finish()
+}
+
+async fn read_text_file(client: &DiscordBotApiClient, attachment: &Attachment) -> Result<String, InteractionError> {
+    let unsupported = InteractionError::UnsupportedAttachment { max_size: MAX_FILE_SIZE };
+    if attachment.size > MAX_FILE_SIZE {
+        return Err(unsupported);
+    }
+    let data = client.download_attachment(attachment).await?;
+    String::from_utf8(data).map_err(|_| unsupported)
+}
+
+async fn set_note(
+    notes: &Notes,
+    target: NoteTarget,
+    key: String,
+    value: String,
+    translator: &Translator,
+) -> CommandHandlerResult {
+    let key = note_key(&key)?;
+    notes.save(target.scope, &key, value, target.visibility, target.ttl).await?;
+    let message = InteractionCallbackMessage {
+        embeds: Some(Box::new([note_saved_embed(&key, translator)?])),
+        ..Default::default()
+    };
+    let callback = InteractionCallback::channel_message_with_source(message);
+    Ok(callback.into())
+}
+
+/// Key trimmed of surrounding whitespace, held to the same length as in the modal
+pub(super) fn note_key(key: &str) -> Result<String, InteractionError> {
+    let key = key.trim();
+    let length = key.chars().count();
+    if length == 0 || length > KEY_MAX_LENGTH.into() {
+        return Err(InteractionError::InvalidKey { max_length: KEY_MAX_LENGTH.into() });
+    }
+    Ok(key.to_owned())
+}
+
+pub struct SetModalSubmitHandler;
+
+impl<C: Get<Notes> + Get<Translator>> ModalSubmitHandler<C> for SetModalSubmitHandler {
+    type Args = (NoteTarget, String, String);
+    type Future = Task<ComponentHandlerResult>;
+
+    fn custom_id_prefix() -> &'static str {
+        SET_MODAL_ID
+    }
+
+    fn parse_args(custom_id_args: &str, values: &ModalSubmitValues) -> Option<Self::Args> {
+        let target = NoteTarget::parse(custom_id_args)?;
+        let key = values.get(KEY_INPUT_ID)?;
+        let value = values.get(VALUE_INPUT_ID)?;
+        Some((target, key.to_string(), value.to_string()))
+    }
+
+    fn handle(&self, (target, key, value): Self::Args, context: &C) -> Self::Future {
+        let notes: Notes = Get::<Notes>::get(context).clone();
+        let translator: Translator = Get::<Translator>::get(context).clone();
         Box::pin(async move {
-            let Self::Args { key, value } = args;
-            store.upsert(&key, &value).await?;
-            let message = InteractionCallbackMessage {
-                content: Some(String::from("Successfully set value for note!")),
-            };
-            let callback = InteractionCallback::channel_message_with_source(message);
-            Ok(callback)
+            target.check_key(&key)?;
+            set_note(&notes, target, key, value, &translator).await
         })
     }
 }
+
+/// "Save as note" in the message context menu: opens the set modal prefilled with the message
+pub struct SaveAsNoteCommandHandler;
+
+impl<C: Get<Translator>> MessageCommandHandler<C> for SaveAsNoteCommandHandler {
+    type Future = Task<CommandHandlerResult>;
+
+    fn name() -> &'static str {
+        "Save as note"
+    }
+
+    fn handle(&self, message: Message, context: &C) -> Self::Future {
+        let value = note_value_of(&message);
+        let modal = set_modal(NoteTarget::default(), None, Some(&value).filter(|v| !v.is_empty()).map(String::as_str), context.get());
+        Box::pin(std::future::ready(Ok(InteractionCallback::modal(modal).into())))
+    }
+}
+
+/// Message content, followed by links to its attachments
+fn note_value_of(message: &Message) -> String {
+    std::iter::once(message.content.as_str())
+        .chain(message.attachments.iter().map(|a| a.url.as_str()))
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{note_key, note_value_of, read_text_file, NoteArgs, NoteTarget, SetCommandHandler};
+    use crate::discord::interaction::message::Message;
+    use crate::discord::interaction::InteractionData;
+    use crate::discord::rest::mock::MockDiscord;
+    use crate::domain::bot::BotContext;
+    use crate::domain::command_handlers::CommandHandler;
+    use crate::domain::interaction_pipeline::InteractionError;
+    use crate::domain::namespace::Scope;
+    use crate::domain::note::{Ttl, Visibility};
+    use serde_json::json;
+
+    fn file_command_data(url: &str, size: u64) -> InteractionData {
+        serde_json::from_value(json!({
+            "id": "1",
+            "name": "set",
+            "type": 1,
+            "options": [{"name": "file", "type": 11, "value": "5"}],
+            "resolved": {"attachments": {"5": {
+                "id": "5",
+                "filename": "todo.txt",
+                "size": size,
+                "url": url,
+                "proxy_url": url
+            }}}
+        }))
+        .unwrap()
+    }
+
+    #[actix_rt::test]
+    async fn uploaded_file_becomes_note() {
+        let discord = MockDiscord::start();
+        let client = discord.client();
+        let data = file_command_data(&format!("{}/attachments/5/todo.txt", discord.base_url()), 2);
+
+        let args = <SetCommandHandler as CommandHandler<BotContext>>::parse_args(&data).unwrap();
+        let NoteArgs::File { key: None, attachment } = args.note else {
+            panic!("file was not recognized");
+        };
+        assert_eq!(attachment.filename, "todo.txt");
+        // mock answers every request with an empty JSON object
+        assert_eq!(read_text_file(&client, &attachment).await.unwrap(), "{}");
+        assert!(discord.requests()[0].path.ends_with("/todo.txt"));
+    }
+
+    #[actix_rt::test]
+    async fn large_file_is_rejected() {
+        let discord = MockDiscord::start();
+        let data = file_command_data("http://localhost/huge.txt", 10 * 1024 * 1024);
+
+        let args = <SetCommandHandler as CommandHandler<BotContext>>::parse_args(&data).unwrap();
+        let NoteArgs::File { attachment, .. } = args.note else {
+            panic!("file was not recognized");
+        };
+        let result = read_text_file(&discord.client(), &attachment).await;
+        assert!(matches!(result, Err(InteractionError::UnsupportedAttachment { .. })));
+        assert!(discord.requests().is_empty());
+    }
+
+    #[test]
+    fn value_without_key_prefills_the_modal() {
+        let data: InteractionData = serde_json::from_value(json!({
+            "id": "1",
+            "name": "set",
+            "type": 1,
+            "options": [{"name": "value", "type": 3, "value": "remember the milk"}]
+        }))
+        .unwrap();
+
+        let args = <SetCommandHandler as CommandHandler<BotContext>>::parse_args(&data).unwrap();
+        let NoteArgs::OpenModal { key: None, value: Some(value) } = args.note else {
+            panic!("value was not kept for the modal");
+        };
+        assert_eq!(value, "remember the milk");
+    }
+
+    #[test]
+    fn keys_are_trimmed_and_limited() {
+        assert_eq!(note_key("  todo ").unwrap(), "todo");
+        assert!(matches!(note_key("   "), Err(InteractionError::InvalidKey { max_length: 100 })));
+        assert!(note_key(&"k".repeat(100)).is_ok());
+        assert!(note_key(&"k".repeat(101)).is_err());
+    }
+
+    #[test]
+    fn note_value_includes_attachments() {
+        let message: Message = serde_json::from_value(json!({
+            "id": "1",
+            "channel_id": "2",
+            "author": {"id": "3", "username": "author"},
+            "content": "look at this",
+            "timestamp": "2022-06-01T12:00:00+00:00",
+            "attachments": [{
+                "id": "4",
+                "filename": "cat.png",
+                "size": 100,
+                "url": "https://cdn.discordapp.com/attachments/2/4/cat.png",
+                "proxy_url": "https://media.discordapp.net/attachments/2/4/cat.png"
+            }]
+        }))
+        .unwrap();
+
+        assert_eq!(
+            note_value_of(&message),
+            "look at this\nhttps://cdn.discordapp.com/attachments/2/4/cat.png"
+        );
+    }
+
+    #[test]
+    fn target_survives_the_modal() {
+        let target = NoteTarget {
+            scope: Scope::Global,
+            visibility: Some(Visibility::Private),
+            ttl: Some(Ttl::minutes(30)),
+            edited_key: None,
+        };
+        assert_eq!(target.custom_id("set"), "set:global:private:ttl30");
+        let args = target.custom_id("set").strip_prefix("set:").unwrap().to_owned();
+        assert_eq!(NoteTarget::parse(&args), Some(target));
+
+        let forever = NoteTarget { ttl: Some(Ttl::Never), ..NoteTarget::default() };
+        assert_eq!(NoteTarget::parse("ttl0"), Some(forever));
+        assert_eq!(NoteTarget::parse(""), Some(NoteTarget::default()));
+        assert!(NoteTarget::parse("ttlx").is_none());
+        assert!(NoteTarget::parse("30m").is_none());
+    }
+
+    #[test]
+    fn edited_key_can_not_be_changed() {
+        let target = NoteTarget::editing(Scope::Global, "todo");
+        let args = target.custom_id("set").strip_prefix("set:").unwrap().to_owned();
+        let target = NoteTarget::parse(&args).unwrap();
+        assert!(target.check_key(" todo ").is_ok());
+        assert!(matches!(target.check_key("done"), Err(InteractionError::KeyChanged)));
+        assert!(NoteTarget::from(Scope::Local).check_key("done").is_ok());
+        assert!(NoteTarget::parse("editx").is_none());
+    }
+}
@@ -1,9 +1,11 @@
-use crate::domain::store::{ListError, ReadError, UpsertError};
+use crate::discord::interaction::embed::EmbedError;
+use crate::discord::interaction::{InteractionCallback, InteractionCallbackMessage};
+use crate::domain::i18n::{Arg, Translator};
+use crate::domain::store::{BackendError, DeleteError, InsertError, ListError, ReadError, UpdateError, UpsertError};
 use actix_web::body::BoxBody;
 use actix_web::http::StatusCode;
 use actix_web::{HttpResponse, ResponseError};
-use log::{debug, error};
-use crate::discord::interaction::{InteractionCallback, InteractionCallbackMessage};
+use log::error;
 
 #[derive(Debug, thiserror::Error)]
 pub enum InteractionError {
@@ -12,76 +14,221 @@ pub enum InteractionError {
     #[error("Unexpected error occurred")]
     Unexpected,
     #[error("Command not implemented")]
+    #[allow(dead_code)]
     CommandNotImplemented,
     #[error("Unknown command")]
+    #[allow(dead_code)]
     UnknownCommand,
     #[error("Invalid command parameters")]
     InvalidCommand,
-    #[error("Key not found")]
-    KeyNotFound,
+    /// `key` is `None` until the handler names the note with [`InteractionError::with_key`]
+    #[error("Note {:?} not found", key)]
+    KeyNotFound { key: Option<String> },
+    #[error("Note {:?} already exists", key)]
+    KeyExists { key: Option<String> },
+    #[error("Access to note {:?} denied", key)]
+    AccessDenied { key: Option<String> },
+    #[error("Note keys must be 1 to {} characters long", max_length)]
+    InvalidKey { max_length: usize },
+    #[error("Notes longer than {} characters can't be edited", max_length)]
+    TooLongToEdit { max_length: usize },
+    #[error("Key of an edited note changed")]
+    KeyChanged,
+    #[error("Revision {} of the note is not kept", number)]
+    RevisionNotFound { number: u32 },
+    #[error("Global notes are reserved to bot admins")]
+    GlobalScopeDenied,
+    #[error("Component belongs to the user who invoked the command")]
+    NotInvoker,
+    #[error("Storage failed to {}: {}", operation, source)]
+    Storage {
+        operation: &'static str,
+        #[source]
+        source: BackendError,
+    },
+    #[error("Invalid embed: {}", .0)]
+    InvalidEmbed(#[from] EmbedError),
+    #[error("Request to Discord failed: {}", .0)]
+    Discord(#[from] reqwest::Error),
+    #[error("Handler panicked: {}", .0)]
+    Panicked(String),
+    #[error("Only UTF-8 text files up to {} KiB are supported", max_size / 1024)]
+    UnsupportedAttachment { max_size: u64 },
+}
+
+impl From<InsertError> for InteractionError {
+    fn from(e: InsertError) -> Self {
+        match e {
+            InsertError::ExistingKey => InteractionError::KeyExists { key: None },
+            InsertError::Backend(source) => InteractionError::Storage { operation: "insert", source },
+        }
+    }
+}
+
+impl From<UpdateError> for InteractionError {
+    fn from(e: UpdateError) -> Self {
+        match e {
+            UpdateError::MissingKey => InteractionError::KeyNotFound { key: None },
+            UpdateError::Backend(source) => InteractionError::Storage { operation: "update", source },
+        }
+    }
 }
 
 impl From<UpsertError> for InteractionError {
-    fn from(_: UpsertError) -> Self {
-        InteractionError::Unexpected
+    fn from(e: UpsertError) -> Self {
+        match e {
+            UpsertError::Backend(source) => InteractionError::Storage { operation: "upsert", source },
+        }
+    }
+}
+
+impl From<DeleteError> for InteractionError {
+    fn from(e: DeleteError) -> Self {
+        match e {
+            DeleteError::MissingKey => InteractionError::KeyNotFound { key: None },
+            // only the sweeper deletes conditionally
+            DeleteError::ChangedValue => InteractionError::Unexpected,
+            DeleteError::Backend(source) => InteractionError::Storage { operation: "delete", source },
+        }
     }
 }
 
 impl From<ListError> for InteractionError {
-    fn from(_: ListError) -> Self {
-        InteractionError::Unexpected
+    fn from(e: ListError) -> Self {
+        match e {
+            ListError::Backend(source) => InteractionError::Storage { operation: "list", source },
+        }
     }
 }
 
 impl From<ReadError> for InteractionError {
     fn from(e: ReadError) -> Self {
         match e {
-            ReadError::MissingKey | ReadError::NoData => InteractionError::KeyNotFound,
-            ReadError::Kv(e) => {
-                debug!("Error in kv: {:#?}", e);
-                InteractionError::Unexpected
-            }
+            ReadError::MissingKey | ReadError::NoData => InteractionError::KeyNotFound { key: None },
+            ReadError::Backend(source) => InteractionError::Storage { operation: "read", source },
         }
     }
 }
 
+/// Only errors which escape the pipeline end up here, they have no locale to reply in.
+/// Discord shows nothing but "interaction failed" for unsuccessful responses,
+/// so even unexpected errors are answered with a message.
 impl ResponseError for InteractionError {
     fn status_code(&self) -> StatusCode {
+        StatusCode::OK
+    }
+
+    fn error_response(&self) -> HttpResponse<BoxBody> {
+        if self.is_unexpected() {
+            error!("Error occured: {}", self);
+        }
+        HttpResponse::build(self.status_code())
+            .json(InteractionCallback::channel_message_with_source(self.user_message(&Translator::default())))
+    }
+}
+
+impl InteractionError {
+    /// Names the note a store error is about
+    pub fn with_key(self, key: &str) -> Self {
         match self {
-            InteractionError::Unexpected | InteractionError::NoHandlerFound => {
-                StatusCode::INTERNAL_SERVER_ERROR
-            }
-            _ => StatusCode::OK,
+            InteractionError::KeyNotFound { .. } => InteractionError::KeyNotFound { key: Some(key.to_owned()) },
+            InteractionError::KeyExists { .. } => InteractionError::KeyExists { key: Some(key.to_owned()) },
+            InteractionError::AccessDenied { .. } => InteractionError::AccessDenied { key: Some(key.to_owned()) },
+            e => e,
         }
     }
 
-    fn error_response(&self) -> HttpResponse<BoxBody> {
-        let mut response = HttpResponse::build(self.status_code());
+    /// Failures which are bugs or outages rather than mistakes of the user
+    pub fn is_unexpected(&self) -> bool {
+        matches!(
+            self,
+            InteractionError::Unexpected
+                | InteractionError::NoHandlerFound
+                | InteractionError::Panicked(_)
+                | InteractionError::Storage { .. }
+                | InteractionError::InvalidEmbed(_)
+                | InteractionError::Discord(_)
+        )
+    }
+
+    /// Missing notes are answered publicly, like the note would have been.
+    /// Everything else only concerns the invoking user.
+    pub fn is_ephemeral(&self) -> bool {
+        !matches!(self, InteractionError::KeyNotFound { .. })
+    }
+
+    /// Message describing the error to the user in their language
+    pub fn user_message(&self, translator: &Translator) -> InteractionCallbackMessage {
+        let message = InteractionCallbackMessage {
+            content: Some(format!("***{}***", self.localized(translator))),
+            ..Default::default()
+        };
+        if self.is_ephemeral() {
+            message.ephemeral()
+        } else {
+            message
+        }
+    }
+
+    fn localized(&self, translator: &Translator) -> String {
         match self {
-            InteractionError::Unexpected | InteractionError::NoHandlerFound => {
-                error!("Error occured: {}", self);
-                response.json(())
+            InteractionError::CommandNotImplemented => translator.text("error-not-implemented"),
+            InteractionError::UnknownCommand => translator.text("error-unknown-command"),
+            InteractionError::InvalidCommand => translator.text("error-invalid-command"),
+            InteractionError::KeyNotFound { key: Some(key) } => {
+                translator.message("error-note-not-found", &[("key", Arg::from(key.as_str()))])
             }
-            InteractionError::CommandNotImplemented => response.json(
-                InteractionCallback::channel_message_with_source(InteractionCallbackMessage {
-                    content: Some(String::from("***This command is not implemented***")),
-                }),
-            ),
-            InteractionError::UnknownCommand => response.json(
-                InteractionCallback::channel_message_with_source(InteractionCallbackMessage {
-                    content: Some(String::from("***This command is unknown***")),
-                }),
-            ),
-            InteractionError::InvalidCommand => response.json(
-                InteractionCallback::channel_message_with_source(InteractionCallbackMessage {
-                    content: Some(String::from("***This command is invalid***")),
-                }),
-            ),
-            e => response.json(InteractionCallback::channel_message_with_source(
-                InteractionCallbackMessage {
-                    content: Some(format!("***{}***", e)),
-                },
-            )),
+            InteractionError::KeyNotFound { key: None } => translator.text("error-note-not-found-unnamed"),
+            InteractionError::KeyExists { key: Some(key) } => {
+                translator.message("error-note-exists", &[("key", Arg::from(key.as_str()))])
+            }
+            InteractionError::KeyExists { key: None } => translator.text("error-note-exists-unnamed"),
+            InteractionError::AccessDenied { key: Some(key) } => {
+                translator.message("error-access-denied", &[("key", Arg::from(key.as_str()))])
+            }
+            InteractionError::AccessDenied { key: None } => translator.text("error-access-denied-unnamed"),
+            InteractionError::InvalidKey { max_length } => {
+                translator.message("error-invalid-key", &[("max-length", Arg::from(*max_length))])
+            }
+            InteractionError::TooLongToEdit { max_length } => {
+                translator.message("error-too-long-to-edit", &[("max-length", Arg::from(*max_length))])
+            }
+            InteractionError::KeyChanged => translator.text("error-key-changed"),
+            InteractionError::RevisionNotFound { number } => {
+                translator.message("error-revision-not-found", &[("revision", Arg::from(u64::from(*number)))])
+            }
+            InteractionError::GlobalScopeDenied => translator.text("error-global-scope-denied"),
+            InteractionError::NotInvoker => translator.text("error-not-invoker"),
+            InteractionError::UnsupportedAttachment { max_size } => {
+                translator.message("error-unsupported-attachment", &[("max-kib", Arg::from(max_size / 1024))])
+            }
+            _ => translator.text("error-unexpected"),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::InteractionError;
+    use crate::discord::interaction::message::MessageFlags;
+    use crate::discord::Locale;
+    use crate::domain::i18n::{Catalog, Translator};
+    use crate::domain::store::ReadError;
+
+    #[test]
+    fn missing_note_is_named_in_users_language() {
+        let error = InteractionError::from(ReadError::NoData).with_key("todo");
+        let translator = Translator::new(Catalog::bundled().unwrap(), [&Locale::German]);
+        let message = error.user_message(&translator);
+        assert_eq!(message.content.as_deref(), Some("***Notiz „todo“ nicht gefunden***"));
+        assert!(!message.flags.contains(MessageFlags::EPHEMERAL));
+    }
+
+    #[test]
+    fn unexpected_errors_do_not_leak_details() {
+        let error = InteractionError::Panicked(String::from("secret"));
+        let message = error.user_message(&Translator::default());
+        assert_eq!(message.content.as_deref(), Some("***Something went wrong on our side***"));
+        assert!(message.flags.contains(MessageFlags::EPHEMERAL));
+    }
+}
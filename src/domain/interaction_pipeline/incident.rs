@@ -0,0 +1,160 @@
+use std::any::Any;
+use std::collections::hash_map::DefaultHasher;
+use std::future::Future;
+use std::hash::{Hash, Hasher};
+use std::panic::AssertUnwindSafe;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use chrono::Utc;
+use futures_util::FutureExt;
+use log::error;
+
+use crate::discord::interaction::embed::{truncate, Embed, DESCRIPTION_LIMIT};
+use crate::discord::interaction::{Interaction, InteractionCallbackMessage};
+use crate::discord::rest::DiscordBotApiClient;
+use crate::domain::i18n::{Arg, Translator};
+use crate::Snowflake;
+use super::{InteractionError, Route};
+
+/// Everything known about an unexpected failure of an interaction
+#[derive(Debug, Clone)]
+pub struct ErrorReport {
+    /// Short id shown to the user, to find the report in logs
+    pub id: String,
+    pub interaction_id: Snowflake,
+    pub route: Option<Route>,
+    pub user_id: Option<Snowflake>,
+    pub guild_id: Option<Snowflake>,
+    pub channel_id: Option<Snowflake>,
+    pub error: String,
+}
+
+impl ErrorReport {
+    /// Report without error yet, for failures which happen after the interaction is gone
+    pub fn template(interaction: &Interaction) -> Self {
+        ErrorReport {
+            id: String::new(),
+            interaction_id: interaction.id,
+            route: Route::of(interaction),
+            user_id: interaction.invoking_user().map(|user| user.id),
+            guild_id: interaction.guild_id,
+            channel_id: interaction.channel_id,
+            error: String::new(),
+        }
+    }
+
+    /// Assigns a fresh id and logs the report
+    pub fn with_error(mut self, error: &InteractionError) -> Self {
+        self.id = error_id(self.interaction_id);
+        self.error = error.to_string();
+        error!(
+            "Error {} in interaction {} ({}) of user {:?} in guild {:?}, channel {:?}: {}",
+            self.id,
+            self.interaction_id,
+            self.route.as_ref().map(ToString::to_string).unwrap_or_default(),
+            self.user_id,
+            self.guild_id,
+            self.channel_id,
+            self.error
+        );
+        self
+    }
+
+    /// Ephemeral reply which lets the user refer to the report
+    pub fn user_message(&self, translator: &Translator) -> InteractionCallbackMessage {
+        let content = translator.message("error-reported", &[("id", Arg::from(self.id.as_str()))]);
+        InteractionCallbackMessage {
+            content: Some(content),
+            ..Default::default()
+        }
+        .ephemeral()
+    }
+}
+
+static ERROR_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+fn error_id(interaction_id: Snowflake) -> String {
+    let mut hasher = DefaultHasher::new();
+    interaction_id.hash(&mut hasher);
+    Utc::now().timestamp_nanos().hash(&mut hasher);
+    ERROR_COUNTER.fetch_add(1, Ordering::Relaxed).hash(&mut hasher);
+    format!("{:08x}", hasher.finish() as u32)
+}
+
+/// Receives reports of unexpected failures, in addition to the log
+pub trait ErrorReporter {
+    fn report(&self, report: &ErrorReport);
+}
+
+/// Posts reports to a channel only admins can see
+#[derive(Clone)]
+pub struct AdminChannelReporter {
+    client: DiscordBotApiClient,
+    channel_id: Snowflake,
+}
+
+impl AdminChannelReporter {
+    pub fn new(client: DiscordBotApiClient, channel_id: Snowflake) -> Self {
+        Self { client, channel_id }
+    }
+}
+
+impl ErrorReporter for AdminChannelReporter {
+    fn report(&self, report: &ErrorReport) {
+        let fields = [
+            ("Interaction", Some(report.interaction_id)),
+            ("User", report.user_id),
+            ("Guild", report.guild_id),
+            ("Channel", report.channel_id),
+        ];
+        let embed = fields
+            .iter()
+            .filter_map(|(name, id)| id.map(|id| (name, id)))
+            .fold(Embed::build(), |embed, (name, id)| {
+                embed.with_inline_field(name, &id.to_string())
+            })
+            .with_title(&format!("Error {}", report.id))
+            .with_description(&truncate(
+                &format!(
+                    "{}\n```\n{}\n```",
+                    report.route.as_ref().map(ToString::to_string).unwrap_or_default(),
+                    report.error
+                ),
+                DESCRIPTION_LIMIT,
+            ))
+            .with_color(0xED4245)
+            .finish();
+        let embed = match embed {
+            Ok(embed) => embed,
+            Err(e) => return error!("Failed to build report {}: {}", report.id, e),
+        };
+        let message = InteractionCallbackMessage {
+            embeds: Some(Box::new([embed])),
+            ..Default::default()
+        };
+        let client = self.client.clone();
+        let channel_id = self.channel_id;
+        let id = report.id.clone();
+        actix_rt::spawn(async move {
+            if let Err(e) = client.create_message(channel_id, &message).await {
+                error!("Failed to post report {}: {}", id, e);
+            }
+        });
+    }
+}
+
+/// Turns a panic while polling `future` into an error
+pub async fn catch_panic<T>(future: impl Future<Output = T>) -> Result<T, InteractionError> {
+    AssertUnwindSafe(future)
+        .catch_unwind()
+        .await
+        .map_err(|payload| InteractionError::Panicked(panic_message(payload)))
+}
+
+pub fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(|message| message.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| String::from("unknown panic"))
+}
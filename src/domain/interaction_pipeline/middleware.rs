@@ -0,0 +1,52 @@
+use log::{info, warn};
+
+use crate::discord::interaction::{Interaction, InteractionCallback};
+use super::{InteractionError, Route};
+
+/// Behavior around every handler of the pipeline.
+///
+/// `before` hooks run in registration order, `after` hooks in reverse order, so the first
+/// registered middleware is the outermost one. When `before` returns a callback, the handlers
+/// and the remaining middlewares are skipped and only middlewares registered earlier see
+/// the result in `after`.
+pub trait Middleware<Context> {
+    /// Inspects the interaction before dispatch, returns a callback to answer it right away
+    fn before(&self, _interaction: &Interaction, _context: &Context) -> Option<InteractionCallback> {
+        None
+    }
+
+    /// Observes or replaces the result going back to Discord
+    fn after(
+        &self,
+        _interaction: &Interaction,
+        _context: &Context,
+        _result: &mut Result<InteractionCallback, InteractionError>,
+    ) {
+    }
+}
+
+/// Logs every interaction and the outcome of its handling
+pub struct LoggingMiddleware;
+
+impl<C> Middleware<C> for LoggingMiddleware {
+    fn before(&self, interaction: &Interaction, _: &C) -> Option<InteractionCallback> {
+        info!(
+            "Interaction {} {:?} from user {:?} in guild {:?}",
+            interaction.id,
+            Route::of(interaction),
+            interaction.invoking_user().map(|user| user.id),
+            interaction.guild_id
+        );
+        None
+    }
+
+    fn after(&self, interaction: &Interaction, _: &C, result: &mut Result<InteractionCallback, InteractionError>) {
+        match result {
+            Ok(callback) => info!(
+                "Interaction {} answered with {:?}",
+                interaction.id, callback.interaction_response_type
+            ),
+            Err(e) => warn!("Interaction {} failed: {}", interaction.id, e),
+        }
+    }
+}
@@ -1,23 +1,63 @@
 mod error;
+mod incident;
+mod middleware;
+mod permission_guard;
+mod router;
 
 
-use futures_util::future::LocalBoxFuture;
+use actix_rt::time::{sleep_until, Instant};
+use futures_util::future::{select, Either, LocalBoxFuture};
+use futures_util::FutureExt;
+use log::{error, warn};
 use std::future::Future;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::rc::Rc;
+use std::time::Duration;
 
 pub use error::InteractionError;
-use crate::discord::interaction::{Interaction, InteractionCallback};
+pub use incident::{AdminChannelReporter, ErrorReport, ErrorReporter};
+pub use middleware::{LoggingMiddleware, Middleware};
+pub use router::{Route, RouteError};
+use incident::{catch_panic, panic_message};
+use permission_guard::check_permissions;
+use router::Router;
+use crate::discord::interaction::message::MessageFlags;
+use crate::discord::interaction::{Interaction, InteractionCallback, InteractionCallbackMessage, InteractionType};
+use crate::discord::rest::{DiscordBotApiClient, InteractionWebhook};
+use crate::discord::Permissions;
+use crate::domain::bot::Get;
+use crate::domain::i18n::Translator;
 
-pub type InteractionHandlerResult = Option<Result<InteractionCallback, InteractionError>>;
+pub type InteractionHandlerResult = Option<Result<InteractionResponse, InteractionError>>;
 
 pub trait InteractionHandler<Context> {
     type Future: Future<Output=InteractionHandlerResult>;
     fn handle(&self, interaction: &Interaction, context: &Context) -> Self::Future;
+
+    /// Interactions the pipeline passes to this handler
+    fn route(&self) -> Route {
+        Route::CatchAll
+    }
+
+    /// Member permissions the pipeline checks before passing an interaction to this handler
+    fn required_permissions(&self) -> Permissions {
+        Permissions::none()
+    }
+
+    /// Permissions the bot needs in the channel, checked by the pipeline like member permissions
+    fn required_bot_permissions(&self) -> Permissions {
+        Permissions::none()
+    }
 }
 pub type Task<T> = LocalBoxFuture<'static, T>;
 
 pub trait NoContextInteractionHandler {
     type Future: Future<Output=InteractionHandlerResult>;
     fn handle(&self, interaction: &Interaction) -> Self::Future;
+
+    fn route(&self) -> Route {
+        Route::CatchAll
+    }
 }
 
 impl<T, C> InteractionHandler<C> for T where T: NoContextInteractionHandler {
@@ -26,35 +66,611 @@ impl<T, C> InteractionHandler<C> for T where T: NoContextInteractionHandler {
     fn handle(&self, interaction: &Interaction, _: &C) -> Self::Future {
         self.handle(interaction)
     }
+
+    fn route(&self) -> Route {
+        NoContextInteractionHandler::route(self)
+    }
+}
+
+pub type DeferredWork = Box<dyn FnOnce(InteractionWebhook) -> Task<Result<InteractionCallbackMessage, InteractionError>>>;
+
+/// Result of a handler: either the callback itself or work which takes
+/// longer than Discord waits for the callback.
+pub enum InteractionResponse {
+    Callback(InteractionCallback),
+    #[allow(dead_code)]
+    Deferred {
+        ephemeral: bool,
+        work: DeferredWork,
+    },
+}
+
+impl InteractionResponse {
+    /// Interaction is acknowledged immediately and `work` is run in the background.
+    /// Its result replaces the original response; the webhook can be used for follow-up messages.
+    #[allow(dead_code)]
+    pub fn deferred<W, F>(work: W) -> Self
+        where W: FnOnce(InteractionWebhook) -> F + 'static,
+              F: Future<Output=Result<InteractionCallbackMessage, InteractionError>> + 'static
+    {
+        Self::defer(false, work)
+    }
+
+    #[allow(dead_code)]
+    pub fn deferred_ephemeral<W, F>(work: W) -> Self
+        where W: FnOnce(InteractionWebhook) -> F + 'static,
+              F: Future<Output=Result<InteractionCallbackMessage, InteractionError>> + 'static
+    {
+        Self::defer(true, work)
+    }
+
+    #[allow(dead_code)]
+    fn defer<W, F>(ephemeral: bool, work: W) -> Self
+        where W: FnOnce(InteractionWebhook) -> F + 'static,
+              F: Future<Output=Result<InteractionCallbackMessage, InteractionError>> + 'static
+    {
+        InteractionResponse::Deferred {
+            ephemeral,
+            work: Box::new(move |webhook| Box::pin(work(webhook))),
+        }
+    }
 }
 
+impl From<InteractionCallback> for InteractionResponse {
+    fn from(callback: InteractionCallback) -> Self {
+        InteractionResponse::Callback(callback)
+    }
+}
 
 impl<TContext> InteractionPipeline<TContext> {
+    /// Fails if two handlers claim the same route
     pub fn new(
         handlers: Vec<
             Box<
                 dyn InteractionHandler<TContext, Future=Task<InteractionHandlerResult>>,
             >,
         >,
-    ) -> Self {
-        Self { handlers }
+    ) -> Result<Self, RouteError> {
+        let router = Router::new(handlers.iter().map(|handler| handler.route()))?;
+        Ok(Self {
+            handlers,
+            router,
+            middlewares: vec![],
+            deferral_budget: None,
+            reporter: None,
+        })
+    }
+
+    /// Unexpected failures are always logged, `reporter` additionally receives their reports
+    pub fn with_error_reporter(mut self, reporter: impl ErrorReporter + 'static) -> Self {
+        self.reporter = Some(Rc::new(reporter));
+        self
+    }
+
+    /// Adds middleware inside of the ones added before
+    pub fn with_middleware(mut self, middleware: impl Middleware<TContext> + 'static) -> Self {
+        self.middlewares.push(Box::new(middleware));
+        self
+    }
+
+    /// Handlers still running when `budget` elapses are deferred automatically:
+    /// Discord is answered with a deferred callback and the result is delivered
+    /// as an edit of the original response once the handler completes.
+    /// The deferral is public as the result is not known yet, ephemeral results
+    /// and errors are delivered as ephemeral follow-ups instead.
+    pub fn with_deferral_budget(mut self, budget: Duration) -> Self {
+        self.deferral_budget = Some(budget);
+        self
     }
+}
+
+impl<TContext: Get<DiscordBotApiClient> + Get<Translator>> InteractionPipeline<TContext> {
     pub async fn handle(
         &self,
         interaction: Interaction,
         context: &TContext,
     ) -> Result<InteractionCallback, InteractionError> {
-        for handler in &self.handlers {
-            if let Some(result) = handler.handle(&interaction, context).await {
-                return result;
+        let mut entered = 0;
+        let mut short_circuit = None;
+        for middleware in &self.middlewares {
+            short_circuit = middleware.before(&interaction, context);
+            if short_circuit.is_some() {
+                break;
+            }
+            entered += 1;
+        }
+        let mut result = match short_circuit {
+            Some(callback) => Ok(callback),
+            None => self.dispatch(&interaction, context).await,
+        };
+        for middleware in self.middlewares[..entered].iter().rev() {
+            middleware.after(&interaction, context, &mut result);
+        }
+        match result {
+            Err(e) if e.is_unexpected() => {
+                let report = self.report(ErrorReport::template(&interaction), &e);
+                let translator: &Translator = context.get();
+                Ok(InteractionCallback::channel_message_with_source(report.user_message(translator)))
+            }
+            result => result,
+        }
+    }
+
+    fn report(&self, template: ErrorReport, error: &InteractionError) -> ErrorReport {
+        report(template, self.reporter.as_deref(), error)
+    }
+
+    async fn dispatch(
+        &self,
+        interaction: &Interaction,
+        context: &TContext,
+    ) -> Result<InteractionCallback, InteractionError> {
+        let deadline = self.deferral_budget.map(|budget| Instant::now() + budget);
+        for index in self.router.candidates(interaction) {
+            let handler = &self.handlers[index];
+            let denied = check_permissions(
+                interaction,
+                &handler.required_permissions(),
+                &handler.required_bot_permissions(),
+                context.get(),
+            );
+            if let Some(callback) = denied {
+                return Ok(callback);
+            }
+            let future = catch_unwind(AssertUnwindSafe(|| handler.handle(interaction, context)))
+                .map_err(|payload| InteractionError::Panicked(panic_message(payload)))?;
+            let future: Task<InteractionHandlerResult> = Box::pin(
+                catch_panic(future).map(|result| result.unwrap_or_else(|e| Some(Err(e)))),
+            );
+            let result = match deadline {
+                None => future.await,
+                Some(deadline) => match select(future, Box::pin(sleep_until(deadline))).await {
+                    Either::Left((result, _)) => result,
+                    Either::Right((_, future)) => {
+                        warn!("Handler exceeded deferral budget, deferring response");
+                        return Ok(self.defer(interaction, context, false, finish_late(future)));
+                    }
+                },
+            };
+            if let Some(result) = result {
+                return result.map(|response| self.respond(response, interaction, context));
             }
         }
         Err(InteractionError::NoHandlerFound)
     }
+
+    /// Turns response into the callback, starting deferred work in the background
+    fn respond(
+        &self,
+        response: InteractionResponse,
+        interaction: &Interaction,
+        context: &TContext,
+    ) -> InteractionCallback {
+        match response {
+            InteractionResponse::Callback(callback) => callback,
+            InteractionResponse::Deferred { ephemeral, work } => {
+                self.defer(interaction, context, ephemeral, work)
+            }
+        }
+    }
+
+    fn defer(
+        &self,
+        interaction: &Interaction,
+        context: &TContext,
+        ephemeral: bool,
+        work: DeferredWork,
+    ) -> InteractionCallback {
+        let client: DiscordBotApiClient = Get::<DiscordBotApiClient>::get(context).clone();
+        let translator: Translator = Get::<Translator>::get(context).clone();
+        let webhook = InteractionWebhook::new(client, &interaction.token);
+        let template = ErrorReport::template(interaction);
+        let reporter = self.reporter.clone();
+        let component = interaction.interaction_type == InteractionType::MessageComponent;
+        actix_rt::spawn(async move {
+            let result = catch_panic(work(webhook.clone())).await.and_then(|result| result);
+            let message = match result {
+                Ok(message) => message,
+                Err(e) if e.is_unexpected() => {
+                    report(template, reporter.as_deref(), &e).user_message(&translator)
+                }
+                Err(e) => e.user_message(&translator),
+            };
+            if let Err(e) = deliver(&webhook, &message, component, ephemeral).await {
+                error!("Failed to deliver deferred response: {}", e);
+            }
+        });
+        if component {
+            InteractionCallback::deferred_update_message()
+        } else {
+            InteractionCallback::deferred_channel_message_with_source(ephemeral)
+        }
+    }
+}
+
+/// Delivers the result of deferred work. Deferred callbacks fix whether the original response
+/// is ephemeral, so ephemeral results of public deferrals are sent as ephemeral follow-ups:
+/// the placeholder of a command is deleted, the message of a component is left as it was.
+async fn deliver(
+    webhook: &InteractionWebhook,
+    message: &InteractionCallbackMessage,
+    component: bool,
+    ephemeral_deferral: bool,
+) -> Result<(), reqwest::Error> {
+    let private = message.flags.contains(MessageFlags::EPHEMERAL) && (component || !ephemeral_deferral);
+    match (private, component) {
+        (false, _) => webhook.edit_original(message).await,
+        (true, false) => {
+            webhook.delete_original().await?;
+            webhook.followup(message).await
+        }
+        (true, true) => webhook.followup(message).await,
+    }
+}
+
+fn report(template: ErrorReport, reporter: Option<&dyn ErrorReporter>, error: &InteractionError) -> ErrorReport {
+    let report = template.with_error(error);
+    if let Some(reporter) = reporter {
+        reporter.report(&report);
+    }
+    report
+}
+
+/// Work which waits for a handler that has already missed the deferral budget.
+/// If that handler turns out not to handle the interaction, other handlers are not tried.
+fn finish_late(handler: Task<InteractionHandlerResult>) -> DeferredWork {
+    Box::new(move |webhook| {
+        Box::pin(async move {
+            match handler.await {
+                Some(Ok(InteractionResponse::Callback(callback))) => {
+                    callback.into_message().ok_or_else(|| {
+                        error!("Callback without message can not be delivered after deferral");
+                        InteractionError::Unexpected
+                    })
+                }
+                Some(Ok(InteractionResponse::Deferred { work, .. })) => work(webhook).await,
+                Some(Err(e)) => Err(e),
+                None => Err(InteractionError::NoHandlerFound),
+            }
+        })
+    })
 }
 
 pub struct InteractionPipeline<TContext> {
     handlers: Vec<
         Box<dyn InteractionHandler<TContext, Future=Task<InteractionHandlerResult>>>,
     >,
+    router: Router,
+    middlewares: Vec<Box<dyn Middleware<TContext>>>,
+    deferral_budget: Option<Duration>,
+    reporter: Option<Rc<dyn ErrorReporter>>,
+}
+
+#[cfg(test)]
+mod tests {
+    use std::future::ready;
+    use std::time::Duration;
+
+    use serde_json::json;
+
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    use super::{ErrorReport, ErrorReporter, InteractionError, InteractionHandlerResult, InteractionPipeline, InteractionResponse, Middleware, NoContextInteractionHandler, Task};
+    use crate::discord::interaction::message::MessageFlags;
+    use crate::discord::interaction::{Interaction, InteractionCallback, InteractionCallbackMessage, InteractionResponseType};
+    use crate::discord::rest::mock::MockDiscord;
+    use crate::discord::rest::DiscordBotApiClient;
+    use crate::domain::bot::Get;
+    use crate::domain::i18n::Translator;
+
+    struct TestContext(DiscordBotApiClient, Translator);
+
+    impl Get<DiscordBotApiClient> for TestContext {
+        fn get(&self) -> &DiscordBotApiClient {
+            &self.0
+        }
+    }
+
+    impl Get<Translator> for TestContext {
+        fn get(&self) -> &Translator {
+            &self.1
+        }
+    }
+
+    struct SlowHandler;
+
+    impl NoContextInteractionHandler for SlowHandler {
+        type Future = Task<InteractionHandlerResult>;
+
+        fn handle(&self, _: &Interaction) -> Self::Future {
+            Box::pin(ready(Some(Ok(InteractionResponse::deferred(|webhook| async move {
+                let progress = InteractionCallbackMessage {
+                    content: Some(String::from("half way")),
+                    ..Default::default()
+                };
+                webhook.followup(&progress).await.unwrap();
+                Ok(InteractionCallbackMessage {
+                    content: Some(String::from("done")),
+                    ..Default::default()
+                })
+            })))))
+        }
+    }
+
+    struct SleepyHandler(Duration);
+
+    impl NoContextInteractionHandler for SleepyHandler {
+        type Future = Task<InteractionHandlerResult>;
+
+        fn handle(&self, _: &Interaction) -> Self::Future {
+            sleepy(self.0, MessageFlags::empty())
+        }
+    }
+
+    struct PrivateSleepyHandler(Duration);
+
+    impl NoContextInteractionHandler for PrivateSleepyHandler {
+        type Future = Task<InteractionHandlerResult>;
+
+        fn handle(&self, _: &Interaction) -> Self::Future {
+            sleepy(self.0, MessageFlags::EPHEMERAL)
+        }
+    }
+
+    fn sleepy(duration: Duration, flags: MessageFlags) -> Task<InteractionHandlerResult> {
+        Box::pin(async move {
+            actix_rt::time::sleep(duration).await;
+            let message = InteractionCallbackMessage {
+                content: Some(String::from("finally")),
+                flags,
+                ..Default::default()
+            };
+            Some(Ok(InteractionCallback::channel_message_with_source(message).into()))
+        })
+    }
+
+    fn command_interaction(name: &str) -> Interaction {
+        serde_json::from_value(json!({
+            "application_id": "1",
+            "id": "2",
+            "token": "interaction-token",
+            "type": 2,
+            "data": {"id": "3", "name": name, "type": 1},
+            "version": 1
+        }))
+        .unwrap()
+    }
+
+    #[actix_rt::test]
+    async fn deferred_response_edits_original_message() {
+        let discord = MockDiscord::start();
+        let pipeline = InteractionPipeline::new(vec![Box::new(SlowHandler)]).unwrap();
+        let context = TestContext(discord.client(), Translator::default());
+
+        let callback = pipeline
+            .handle(command_interaction("export"), &context)
+            .await
+            .unwrap();
+        assert!(matches!(
+            callback.interaction_response_type,
+            InteractionResponseType::DeferredChannelMessageWithSource
+        ));
+
+        let requests = discord.wait_for_requests(2).await;
+        assert_eq!(requests.len(), 2);
+        assert_eq!(requests[0].method, "POST");
+        assert_eq!(requests[0].path, "/v10/webhooks/1/interaction-token");
+        assert_eq!(requests[1].method, "PATCH");
+        assert_eq!(requests[1].path, "/v10/webhooks/1/interaction-token/messages/@original");
+        let body: serde_json::Value = serde_json::from_slice(&requests[1].body).unwrap();
+        assert_eq!(body["content"], json!("done"));
+    }
+
+    #[actix_rt::test]
+    async fn slow_handler_is_deferred_after_budget() {
+        let discord = MockDiscord::start();
+        let pipeline = InteractionPipeline::new(vec![Box::new(SleepyHandler(Duration::from_millis(200)))])
+            .unwrap()
+            .with_deferral_budget(Duration::from_millis(20));
+        let context = TestContext(discord.client(), Translator::default());
+
+        let callback = pipeline
+            .handle(command_interaction("get"), &context)
+            .await
+            .unwrap();
+        assert!(matches!(
+            callback.interaction_response_type,
+            InteractionResponseType::DeferredChannelMessageWithSource
+        ));
+
+        let requests = discord.wait_for_requests(1).await;
+        assert_eq!(requests[0].method, "PATCH");
+        let body: serde_json::Value = serde_json::from_slice(&requests[0].body).unwrap();
+        assert_eq!(body["content"], json!("finally"));
+    }
+
+    #[actix_rt::test]
+    async fn ephemeral_result_of_deferred_command_is_followed_up_privately() {
+        let discord = MockDiscord::start();
+        let pipeline = InteractionPipeline::new(vec![Box::new(PrivateSleepyHandler(Duration::from_millis(200)))])
+            .unwrap()
+            .with_deferral_budget(Duration::from_millis(20));
+        let context = TestContext(discord.client(), Translator::default());
+
+        pipeline
+            .handle(command_interaction("get"), &context)
+            .await
+            .unwrap();
+
+        let requests = discord.wait_for_requests(2).await;
+        assert_eq!(requests[0].method, "DELETE");
+        assert!(requests[0].path.ends_with("/messages/@original"));
+        assert_eq!(requests[1].method, "POST");
+        let body: serde_json::Value = serde_json::from_slice(&requests[1].body).unwrap();
+        assert_eq!(body["flags"], json!(64));
+        assert_eq!(body["content"], json!("finally"));
+    }
+
+    #[actix_rt::test]
+    async fn fast_handler_is_answered_directly() {
+        let discord = MockDiscord::start();
+        let pipeline = InteractionPipeline::new(vec![Box::new(SleepyHandler(Duration::from_millis(1)))])
+            .unwrap()
+            .with_deferral_budget(Duration::from_secs(1));
+        let context = TestContext(discord.client(), Translator::default());
+
+        let callback = pipeline
+            .handle(command_interaction("get"), &context)
+            .await
+            .unwrap();
+        assert!(matches!(
+            callback.interaction_response_type,
+            InteractionResponseType::ChannelMessageWithSource
+        ));
+        assert!(discord.requests().is_empty());
+    }
+
+    /// Records hook calls into a shared log, optionally answering in `before`
+    struct Recorder {
+        name: &'static str,
+        log: Rc<RefCell<Vec<String>>>,
+        short_circuit: bool,
+    }
+
+    impl<C> Middleware<C> for Recorder {
+        fn before(&self, _: &Interaction, _: &C) -> Option<InteractionCallback> {
+            self.log.borrow_mut().push(format!("before {}", self.name));
+            self.short_circuit.then(|| {
+                let message = InteractionCallbackMessage {
+                    content: Some(String::from("maintenance")),
+                    ..Default::default()
+                };
+                InteractionCallback::channel_message_with_source(message)
+            })
+        }
+
+        fn after(&self, _: &Interaction, _: &C, result: &mut Result<InteractionCallback, InteractionError>) {
+            self.log.borrow_mut().push(format!("after {}", self.name));
+            if self.name == "outer" {
+                *result = Err(InteractionError::InvalidCommand);
+            }
+        }
+    }
+
+    fn recorder(name: &'static str, log: &Rc<RefCell<Vec<String>>>, short_circuit: bool) -> Recorder {
+        Recorder {
+            name,
+            log: log.clone(),
+            short_circuit,
+        }
+    }
+
+    #[actix_rt::test]
+    async fn middlewares_wrap_handler_in_order() {
+        let discord = MockDiscord::start();
+        let log = Rc::new(RefCell::new(vec![]));
+        let pipeline = InteractionPipeline::new(vec![Box::new(SleepyHandler(Duration::from_millis(1)))])
+            .unwrap()
+            .with_middleware(recorder("outer", &log, false))
+            .with_middleware(recorder("inner", &log, false));
+        let context = TestContext(discord.client(), Translator::default());
+
+        let result = pipeline.handle(command_interaction("get"), &context).await;
+
+        assert_eq!(*log.borrow(), ["before outer", "before inner", "after inner", "after outer"]);
+        // outer middleware replaced the result
+        assert!(matches!(result, Err(InteractionError::InvalidCommand)));
+    }
+
+    #[actix_rt::test]
+    async fn middleware_short_circuits_dispatch() {
+        let discord = MockDiscord::start();
+        let log = Rc::new(RefCell::new(vec![]));
+        let pipeline = InteractionPipeline::new(vec![Box::new(SleepyHandler(Duration::from_secs(10)))])
+            .unwrap()
+            .with_middleware(recorder("first", &log, true))
+            .with_middleware(recorder("second", &log, false));
+        let context = TestContext(discord.client(), Translator::default());
+
+        let callback = pipeline
+            .handle(command_interaction("get"), &context)
+            .await
+            .unwrap();
+
+        assert_eq!(*log.borrow(), ["before first"]);
+        assert_eq!(callback.into_message().unwrap().content.as_deref(), Some("maintenance"));
+    }
+
+    struct PanickingHandler {
+        deferred: bool,
+    }
+
+    impl NoContextInteractionHandler for PanickingHandler {
+        type Future = Task<InteractionHandlerResult>;
+
+        fn handle(&self, _: &Interaction) -> Self::Future {
+            if !self.deferred {
+                return Box::pin(async { panic!("note store is gone") });
+            }
+            Box::pin(ready(Some(Ok(InteractionResponse::deferred(|_| async {
+                panic!("note store is gone")
+            })))))
+        }
+    }
+
+    struct CollectingReporter(Rc<RefCell<Vec<ErrorReport>>>);
+
+    impl ErrorReporter for CollectingReporter {
+        fn report(&self, report: &ErrorReport) {
+            self.0.borrow_mut().push(report.clone());
+        }
+    }
+
+    #[actix_rt::test]
+    async fn panic_is_reported_with_error_id() {
+        let discord = MockDiscord::start();
+        let reports = Rc::new(RefCell::new(vec![]));
+        let pipeline = InteractionPipeline::new(vec![Box::new(PanickingHandler { deferred: false })])
+            .unwrap()
+            .with_error_reporter(CollectingReporter(reports.clone()));
+        let context = TestContext(discord.client(), Translator::default());
+
+        let message = pipeline
+            .handle(command_interaction("get"), &context)
+            .await
+            .unwrap()
+            .into_message()
+            .unwrap();
+
+        let reports = reports.borrow();
+        assert_eq!(reports.len(), 1);
+        assert_eq!(reports[0].error, "Handler panicked: note store is gone");
+        assert_eq!(reports[0].route.as_ref().unwrap().to_string(), "command:1:get");
+        assert!(message.flags.contains(MessageFlags::EPHEMERAL));
+        assert!(message.content.unwrap().contains(&reports[0].id));
+    }
+
+    #[actix_rt::test]
+    async fn deferred_panic_is_reported_privately() {
+        let discord = MockDiscord::start();
+        let reports = Rc::new(RefCell::new(vec![]));
+        let pipeline = InteractionPipeline::new(vec![Box::new(PanickingHandler { deferred: true })])
+            .unwrap()
+            .with_error_reporter(CollectingReporter(reports.clone()));
+        let context = TestContext(discord.client(), Translator::default());
+
+        pipeline
+            .handle(command_interaction("get"), &context)
+            .await
+            .unwrap();
+
+        let requests = discord.wait_for_requests(2).await;
+        assert_eq!(requests[0].method, "DELETE");
+        assert_eq!(requests[1].method, "POST");
+        let body: serde_json::Value = serde_json::from_slice(&requests[1].body).unwrap();
+        assert_eq!(body["flags"], json!(64));
+        let id = reports.borrow()[0].id.clone();
+        assert!(body["content"].as_str().unwrap().contains(&id));
+    }
 }
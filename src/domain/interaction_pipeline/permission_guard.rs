@@ -0,0 +1,115 @@
+use crate::discord::interaction::{Interaction, InteractionCallback, InteractionCallbackMessage};
+use crate::discord::{Permissions, PermissionsProvider};
+use crate::domain::i18n::{Arg, Translator};
+
+/// Ephemeral reply listing missing permissions, `None` when everything required is granted.
+/// Outside of guilds there is no member, so commands requiring member permissions are refused.
+pub fn check_permissions(
+    interaction: &Interaction,
+    required: &Permissions,
+    required_bot: &Permissions,
+    translator: &Translator,
+) -> Option<InteractionCallback> {
+    let member_missing = match interaction.member.as_ref().and_then(|m| m.permissions.as_ref()) {
+        Some(granted) => missing(granted, required),
+        None => required.clone(),
+    };
+    let bot_missing = match &interaction.app_permissions {
+        Some(granted) => missing(granted, required_bot),
+        None => Permissions::none(),
+    };
+    if member_missing.is_empty() && bot_missing.is_empty() {
+        return None;
+    }
+
+    let mut lines = vec![];
+    if !member_missing.is_empty() {
+        lines.push(translator.message("missing-member-permissions", &[("permissions", list(&member_missing))]));
+    }
+    if !bot_missing.is_empty() {
+        lines.push(translator.message("missing-bot-permissions", &[("permissions", list(&bot_missing))]));
+    }
+    let message = InteractionCallbackMessage {
+        content: Some(lines.join("\n")),
+        ..Default::default()
+    };
+    Some(InteractionCallback::channel_message_with_source(message.ephemeral()))
+}
+
+/// Administrators are granted everything
+fn missing(granted: &Permissions, required: &Permissions) -> Permissions {
+    if granted.allowed_to_administrator() {
+        Permissions::none()
+    } else {
+        granted.missing(required)
+    }
+}
+
+fn list(permissions: &Permissions) -> Arg {
+    let names: Vec<String> = permissions.names().iter().map(|name| format!("**{}**", name)).collect();
+    Arg::from(names.join(", "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::check_permissions;
+    use crate::discord::interaction::Interaction;
+    use crate::discord::{Permissions, PermissionsMut};
+    use crate::domain::i18n::Translator;
+    use serde_json::{json, Value};
+
+    fn guild_interaction(member_permissions: &str, app_permissions: &str) -> Interaction {
+        let value: Value = json!({
+            "application_id": "1",
+            "id": "2",
+            "token": "token",
+            "type": 2,
+            "guild_id": "3",
+            "member": {
+                "user": {"id": "4", "username": "user"},
+                "joined_at": "2022-06-01T12:00:00+00:00",
+                "permissions": member_permissions
+            },
+            "app_permissions": app_permissions,
+            "version": 1
+        });
+        serde_json::from_value(value).unwrap()
+    }
+
+    fn manage_messages() -> Permissions {
+        let mut permissions = PermissionsMut::new();
+        permissions.allow_to_manage_messages();
+        permissions.freeze()
+    }
+
+    fn attach_files() -> Permissions {
+        let mut permissions = PermissionsMut::new();
+        permissions.allow_to_attach_files();
+        permissions.freeze()
+    }
+
+    #[test]
+    fn granted_permissions_pass() {
+        // 8192 is Manage Messages, 32768 is Attach Files
+        let interaction = guild_interaction("8192", "32768");
+        assert!(check_permissions(&interaction, &manage_messages(), &attach_files(), &Translator::default()).is_none());
+    }
+
+    #[test]
+    fn administrator_has_every_permission() {
+        let interaction = guild_interaction("8", "0");
+        assert!(check_permissions(&interaction, &manage_messages(), &Permissions::none(), &Translator::default()).is_none());
+    }
+
+    #[test]
+    fn missing_permissions_are_listed() {
+        let interaction = guild_interaction("0", "0");
+        let callback = check_permissions(&interaction, &manage_messages(), &attach_files(), &Translator::default()).unwrap();
+        let message = serde_json::to_value(callback).unwrap()["data"].clone();
+        assert_eq!(
+            message["content"],
+            json!("You are missing permissions: **Manage Messages**\nI am missing permissions: **Attach Files**")
+        );
+        assert_eq!(message["flags"], json!(64));
+    }
+}
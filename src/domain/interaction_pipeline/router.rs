@@ -0,0 +1,154 @@
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+
+use crate::discord::interaction::{Interaction, InteractionType};
+use crate::discord::rest::application_command::ApplicationCommandType;
+use crate::domain::component_handlers::split_custom_id;
+
+/// Interactions a handler is responsible for
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub enum Route {
+    Ping,
+    Command {
+        command_type: ApplicationCommandType,
+        name: String,
+    },
+    /// Message components with given `custom_id` prefix
+    Component(String),
+    /// Modals with given `custom_id` prefix
+    ModalSubmit(String),
+    /// Tried in registration order when no other route matches
+    CatchAll,
+}
+
+impl Route {
+    pub fn command(name: &str) -> Self {
+        Route::Command {
+            command_type: ApplicationCommandType::ChatInput,
+            name: name.to_owned(),
+        }
+    }
+
+    /// Route of a specific interaction, `None` for interactions only catch-all handlers take
+    pub fn of(interaction: &Interaction) -> Option<Self> {
+        let data = interaction.data.as_ref();
+        match interaction.interaction_type {
+            InteractionType::Ping => Some(Route::Ping),
+            InteractionType::ApplicationCommand => data.map(|d| Route::Command {
+                command_type: d.command_type.unwrap_or_default(),
+                name: d.name.clone(),
+            }),
+            InteractionType::MessageComponent => data
+                .and_then(|d| d.custom_id.as_deref())
+                .map(|id| Route::Component(split_custom_id(id).0.to_owned())),
+            InteractionType::ModalSubmit => data
+                .and_then(|d| d.custom_id.as_deref())
+                .map(|id| Route::ModalSubmit(split_custom_id(id).0.to_owned())),
+            _ => None,
+        }
+    }
+}
+
+/// Stable textual form, e.g. `command:1:set` or `component:ls`
+impl Display for Route {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Route::Ping => write!(f, "ping"),
+            Route::Command { command_type, name } => write!(f, "command:{}:{}", *command_type as u8, name),
+            Route::Component(prefix) => write!(f, "component:{}", prefix),
+            Route::ModalSubmit(prefix) => write!(f, "modal:{}", prefix),
+            Route::CatchAll => write!(f, "*"),
+        }
+    }
+}
+
+#[derive(Debug, thiserror::Error, Eq, PartialEq)]
+pub enum RouteError {
+    #[error("More than one handler is registered for {}", .0)]
+    Duplicate(Route),
+}
+
+/// Index of handlers by route
+pub struct Router {
+    routes: HashMap<Route, usize>,
+    catch_all: Vec<usize>,
+}
+
+impl Router {
+    pub fn new(routes: impl IntoIterator<Item = Route>) -> Result<Self, RouteError> {
+        let mut router = Router {
+            routes: HashMap::new(),
+            catch_all: vec![],
+        };
+        for (index, route) in routes.into_iter().enumerate() {
+            match route {
+                Route::CatchAll => router.catch_all.push(index),
+                route => {
+                    if router.routes.contains_key(&route) {
+                        return Err(RouteError::Duplicate(route));
+                    }
+                    router.routes.insert(route, index);
+                }
+            }
+        }
+        Ok(router)
+    }
+
+    /// Indices of handlers to try in order: the routed one, then catch-all handlers
+    pub fn candidates(&self, interaction: &Interaction) -> impl Iterator<Item = usize> + '_ {
+        Route::of(interaction)
+            .and_then(|route| self.routes.get(&route).copied())
+            .into_iter()
+            .chain(self.catch_all.iter().copied())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Route, RouteError, Router};
+    use crate::discord::interaction::Interaction;
+    use serde_json::{json, Value};
+
+    fn interaction(interaction_type: u8, data: Value) -> Interaction {
+        serde_json::from_value(json!({
+            "application_id": "1",
+            "id": "2",
+            "token": "token",
+            "type": interaction_type,
+            "data": data,
+            "version": 1
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn reject_duplicate_routes() {
+        let result = Router::new([Route::command("ls"), Route::Ping, Route::command("ls")]);
+        assert_eq!(result.err(), Some(RouteError::Duplicate(Route::command("ls"))));
+    }
+
+    #[test]
+    fn catch_all_handlers_may_repeat() {
+        assert!(Router::new([Route::CatchAll, Route::CatchAll]).is_ok());
+    }
+
+    #[test]
+    fn route_by_name_then_catch_all() {
+        let router = Router::new([
+            Route::CatchAll,
+            Route::command("get"),
+            Route::Component(String::from("ls")),
+            Route::command("ls"),
+        ])
+        .unwrap();
+
+        let ls = interaction(2, json!({"id": "3", "name": "ls", "type": 1}));
+        assert_eq!(router.candidates(&ls).collect::<Vec<_>>(), [3, 0]);
+
+        let page = interaction(3, json!({"custom_id": "ls:2", "component_type": 2}));
+        assert_eq!(router.candidates(&page).collect::<Vec<_>>(), [2, 0]);
+
+        let unknown = interaction(2, json!({"id": "3", "name": "unknown", "type": 1}));
+        assert_eq!(router.candidates(&unknown).collect::<Vec<_>>(), [0]);
+    }
+}
@@ -2,8 +2,15 @@ mod commands;
 pub mod store;
 pub mod interaction_pipeline;
 mod command_handlers;
+pub mod component_handlers;
 pub mod bot;
+pub mod cooldown;
+pub mod diff;
+pub mod i18n;
+pub mod namespace;
+pub mod note;
+pub mod paginator;
+pub mod sweeper;
 pub mod interaction_handlers;
-mod tournament;
 
 pub use commands::declare_commands;
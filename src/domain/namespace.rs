@@ -0,0 +1,523 @@
+use chrono::Utc;
+
+use crate::discord::interaction::{Interaction, InteractionData};
+use crate::discord::Snowflake;
+use crate::domain::component_handlers::{custom_id, split_custom_id};
+use crate::domain::interaction_pipeline::InteractionError;
+use crate::domain::note::{Note, Ttl, Visibility};
+use crate::domain::store::{DeleteError, ReadError, Storage};
+
+const GLOBAL: &str = "global";
+
+/// Part of the storage a note lives in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Namespace {
+    Guild(Snowflake),
+    /// Notes of a user in direct messages
+    User(Snowflake),
+    /// Notes shared by all guilds, managed by bot admins
+    Global,
+}
+
+impl Namespace {
+    /// Guild of the interaction, or the invoking user in direct messages
+    pub fn of(interaction: &Interaction) -> Option<Self> {
+        match interaction.guild_id {
+            Some(guild_id) => Some(Namespace::Guild(guild_id)),
+            None => interaction.invoking_user().map(|user| Namespace::User(user.id)),
+        }
+    }
+
+    /// Namespace a named bucket belongs to, `None` for buckets of other data
+    pub fn of_bucket(name: &str) -> Option<Self> {
+        let (kind, id) = name.split_once(':')?;
+        match kind {
+            "guild" => Some(Namespace::Guild(id.parse().ok()?)),
+            "user" => Some(Namespace::User(id.parse().ok()?)),
+            _ => None,
+        }
+    }
+
+    /// Bucket of the namespace. Global notes stay in the default bucket,
+    /// where all notes were kept before namespaces.
+    pub fn bucket(&self, storage: &Storage) -> Storage {
+        match self {
+            Namespace::Guild(id) => storage.bucket(&format!("guild:{}", id)),
+            Namespace::User(id) => storage.bucket(&format!("user:{}", id)),
+            Namespace::Global => storage.clone(),
+        }
+    }
+}
+
+/// Namespace a command works on, chosen by its `global` option
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Scope {
+    /// Guild of the interaction, or the user in direct messages
+    #[default]
+    Local,
+    Global,
+}
+
+impl Scope {
+    /// Scope of the `global` option, `None` if the option is not a boolean
+    pub fn from_options(interaction_data: &InteractionData) -> Option<Self> {
+        match interaction_data.option(GLOBAL) {
+            Some(global) => Some(if global.as_bool()? { Scope::Global } else { Scope::Local }),
+            None => Some(Scope::Local),
+        }
+    }
+
+    /// Custom id of a component which acts on notes of this scope, see [`Scope::split_custom_id_args`]
+    pub fn custom_id(&self, prefix: &str, args: &str) -> String {
+        match (self, args) {
+            (Scope::Local, "") => prefix.to_owned(),
+            (Scope::Local, args) => custom_id(prefix, args),
+            (Scope::Global, "") => custom_id(prefix, GLOBAL),
+            (Scope::Global, args) => custom_id(prefix, &custom_id(GLOBAL, args)),
+        }
+    }
+
+    /// Scope and remaining arguments of a custom id made by [`Scope::custom_id`]
+    pub fn split_custom_id_args(custom_id_args: &str) -> (Self, &str) {
+        match split_custom_id(custom_id_args) {
+            (GLOBAL, args) => (Scope::Global, args),
+            _ => (Scope::Local, custom_id_args),
+        }
+    }
+}
+
+/// User an interaction was invoked by
+#[derive(Debug, Clone, Copy)]
+pub struct Requester {
+    pub user_id: Snowflake,
+    /// Bot admins manage global notes
+    pub admin: bool,
+    /// Members with Manage Messages may edit and delete notes of others in their guild
+    pub moderator: bool,
+}
+
+/// Notes reachable from one interaction, with access control applied
+#[derive(Clone)]
+pub struct Notes {
+    storage: Storage,
+    local: Option<Namespace>,
+    requester: Option<Requester>,
+}
+
+impl Notes {
+    /// `local` and `requester` are `None` outside of interactions
+    pub fn new(storage: Storage, local: Option<Namespace>, requester: Option<Requester>) -> Self {
+        Self { storage, local, requester }
+    }
+
+    /// User the notes are accessed for, `None` outside of interactions
+    pub fn requester_id(&self) -> Option<Snowflake> {
+        self.requester.map(|r| r.user_id)
+    }
+
+    fn bucket(&self, scope: Scope) -> Result<Storage, InteractionError> {
+        match scope {
+            Scope::Local => self
+                .local
+                .map(|namespace| namespace.bucket(&self.storage))
+                .ok_or(InteractionError::InvalidCommand),
+            Scope::Global => Ok(Namespace::Global.bucket(&self.storage)),
+        }
+    }
+
+    fn is_admin(&self) -> bool {
+        self.requester.is_some_and(|r| r.admin)
+    }
+
+    fn can_read(&self, scope: Scope, note: &Note) -> bool {
+        match note.visibility {
+            Visibility::Private => self.requester.is_some_and(|r| note.is_author(r.user_id)),
+            Visibility::Guild => scope == Scope::Local || self.is_admin(),
+            Visibility::Public => true,
+        }
+    }
+
+    fn can_edit(&self, scope: Scope, note: &Note) -> bool {
+        self.requester.is_some_and(|r| match scope {
+            Scope::Local => note.is_author(r.user_id) || r.moderator,
+            Scope::Global => r.admin,
+        })
+    }
+
+    /// Bucket of `scope` for changes, global notes are changed by bot admins only
+    fn writable_bucket(&self, scope: Scope) -> Result<(Requester, Storage), InteractionError> {
+        let requester = self.requester.ok_or(InteractionError::InvalidCommand)?;
+        if scope == Scope::Global && !requester.admin {
+            return Err(InteractionError::GlobalScopeDenied);
+        }
+        Ok((requester, self.bucket(scope)?))
+    }
+
+    /// Note `key`, `None` when it is missing or expired
+    async fn load(store: &Storage, key: &str) -> Result<Option<Note>, InteractionError> {
+        match store.read(key).await {
+            Ok(stored) => Ok(Some(Note::decode(stored)).filter(|note| !note.is_expired(Utc::now()))),
+            Err(ReadError::MissingKey | ReadError::NoData) => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Note `key` if the requester may edit it
+    /// Expired notes free their key, unless they were replaced meanwhile
+    async fn clear_expired(store: &Storage, key: &str) -> Result<(), InteractionError> {
+        let stored = match store.read(key).await {
+            Ok(stored) => stored,
+            Err(ReadError::MissingKey | ReadError::NoData) => return Ok(()),
+            Err(e) => return Err(e.into()),
+        };
+        if !Note::decode(stored.clone()).is_expired(Utc::now()) {
+            return Ok(());
+        }
+        match store.delete_if(key, &stored).await {
+            Ok(()) | Err(DeleteError::MissingKey | DeleteError::ChangedValue) => Ok(()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    async fn read_editable(&self, store: &Storage, scope: Scope, key: &str) -> Result<Note, InteractionError> {
+        let note = Self::load(store, key)
+            .await?
+            .ok_or_else(|| InteractionError::KeyNotFound { key: Some(key.to_owned()) })?;
+        if !self.can_edit(scope, &note) {
+            return Err(InteractionError::AccessDenied { key: Some(key.to_owned()) });
+        }
+        Ok(note)
+    }
+
+    /// Note `key`, denied when its visibility excludes the requester
+    pub async fn read(&self, scope: Scope, key: &str) -> Result<Note, InteractionError> {
+        let note = Self::load(&self.bucket(scope)?, key)
+            .await?
+            .ok_or_else(|| InteractionError::KeyNotFound { key: Some(key.to_owned()) })?;
+        if !self.can_read(scope, &note) {
+            return Err(InteractionError::AccessDenied { key: Some(key.to_owned()) });
+        }
+        Ok(note)
+    }
+
+    /// Notes the requester may read, in ascending order of keys
+    async fn readable(&self, scope: Scope) -> Result<Vec<(String, Note)>, InteractionError> {
+        let store = self.bucket(scope)?;
+        let mut notes = vec![];
+        for key in store.list().await? {
+            // deleted since listing, or expired
+            let Some(note) = Self::load(&store, &key).await? else {
+                continue;
+            };
+            if self.can_read(scope, &note) {
+                notes.push((key, note));
+            }
+        }
+        Ok(notes)
+    }
+
+    /// Keys of notes the requester may read with their visibility, in ascending order of keys
+    pub async fn list(&self, scope: Scope) -> Result<Vec<(String, Visibility)>, InteractionError> {
+        let notes = self.readable(scope).await?;
+        Ok(notes.into_iter().map(|(key, note)| (key, note.visibility)).collect())
+    }
+
+    /// Like [`Notes::list`], limited to notes written by `author_id`
+    pub async fn authored_by(
+        &self,
+        scope: Scope,
+        author_id: Snowflake,
+    ) -> Result<Vec<(String, Visibility)>, InteractionError> {
+        let notes = self.readable(scope).await?;
+        Ok(notes
+            .into_iter()
+            .filter(|(_, note)| note.is_author(author_id))
+            .map(|(key, note)| (key, note.visibility))
+            .collect())
+    }
+
+    /// Keys of all unexpired notes of `scope`, including notes hidden from the requester.
+    /// Only for resolving references to notes, never shown to users.
+    pub async fn keys(&self, scope: Scope) -> Result<Vec<String>, InteractionError> {
+        let store = self.bucket(scope)?;
+        let mut keys = vec![];
+        for key in store.list().await? {
+            if Self::load(&store, &key).await?.is_some() {
+                keys.push(key);
+            }
+        }
+        Ok(keys)
+    }
+
+    /// Note `key`, denied unless the requester may edit it
+    pub async fn editable(&self, scope: Scope, key: &str) -> Result<Note, InteractionError> {
+        let (_, store) = self.writable_bucket(scope)?;
+        self.read_editable(&store, scope, key).await
+    }
+
+    /// Deletes note `key` if the requester may edit it
+    pub async fn delete(&self, scope: Scope, key: &str) -> Result<(), InteractionError> {
+        let (_, store) = self.writable_bucket(scope)?;
+        self.read_editable(&store, scope, key).await?;
+        store.delete(key).await.map_err(|e| InteractionError::from(e).with_key(key))
+    }
+
+    /// Moves note `from` to the free key `to`, keeping its author and visibility
+    pub async fn rename(&self, scope: Scope, from: &str, to: &str) -> Result<(), InteractionError> {
+        let (_, store) = self.writable_bucket(scope)?;
+        let note = self.read_editable(&store, scope, from).await?;
+        Self::clear_expired(&store, to).await?;
+        let note = Note { updated_at: Utc::now(), ..note };
+        // a note saved under `to` meanwhile is not overwritten
+        store.insert(to, &note.encode()).await.map_err(|e| InteractionError::from(e).with_key(to))?;
+        if let Err(e) = store.delete(from).await {
+            // the note must not end up under both keys
+            if let Err(rollback) = store.delete(to).await {
+                log::error!("Failed to roll back renaming {:?} to {:?}: {}", from, to, rollback);
+            }
+            return Err(InteractionError::from(e).with_key(from));
+        }
+        Ok(())
+    }
+
+    /// Makes revision `number` of note `key` its current value again
+    pub async fn restore(&self, scope: Scope, key: &str, number: u32) -> Result<Note, InteractionError> {
+        let (requester, store) = self.writable_bucket(scope)?;
+        let note = self
+            .read_editable(&store, scope, key)
+            .await?
+            .restored(number, requester.user_id, Utc::now())
+            .ok_or(InteractionError::RevisionNotFound { number })?;
+        // deleted meanwhile, the restore must not bring it back
+        store.update(key, &note.encode()).await.map_err(|e| InteractionError::from(e).with_key(key))?;
+        Ok(note)
+    }
+
+    /// Creates note `key` owned by the requester, or updates it if the requester may edit it.
+    /// Global notes are written by bot admins only.
+    /// `visibility` and `ttl` of an existing note are kept when `None`.
+    pub async fn save(
+        &self,
+        scope: Scope,
+        key: &str,
+        value: String,
+        visibility: Option<Visibility>,
+        ttl: Option<Ttl>,
+    ) -> Result<Note, InteractionError> {
+        let (requester, store) = self.writable_bucket(scope)?;
+        let now = Utc::now();
+        let existing = Self::load(&store, key).await?;
+        let exists = existing.is_some();
+        let note = match existing {
+            Some(note) => {
+                if !self.can_edit(scope, &note) {
+                    return Err(InteractionError::AccessDenied { key: Some(key.to_owned()) });
+                }
+                note.updated(value, visibility, requester.user_id, now)
+            }
+            None => Note::new(value, requester.user_id, visibility.unwrap_or_default(), now),
+        };
+        let note = match ttl {
+            Some(ttl) => note.with_ttl(ttl, now),
+            None => note,
+        };
+        if exists {
+            // deleted meanwhile, the edit must not bring it back
+            store.update(key, &note.encode()).await.map_err(|e| InteractionError::from(e).with_key(key))?;
+        } else {
+            // expired notes free their key
+            store.upsert(key, &note.encode()).await?;
+        }
+        Ok(note)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Namespace, Notes, Requester, Scope};
+    use crate::discord::Snowflake;
+    use crate::domain::interaction_pipeline::InteractionError;
+    use crate::domain::note::{Note, Ttl, Visibility};
+    use chrono::{Duration, Utc};
+    use crate::domain::store::{
+        DeleteError, InsertError, ListError, NoteStore, ReadError, Storage, UpdateError, UpsertError,
+    };
+    use async_trait::async_trait;
+
+    /// Store refusing to delete `sticky` and reading `hidden` as missing, as if it was saved concurrently
+    struct FlakyStore {
+        inner: Storage,
+        sticky: &'static str,
+        hidden: &'static str,
+    }
+
+    #[async_trait(?Send)]
+    impl NoteStore for FlakyStore {
+        async fn insert(&self, key: &str, value: &str) -> Result<(), InsertError> {
+            self.inner.insert(key, value).await
+        }
+
+        async fn update(&self, key: &str, value: &str) -> Result<(), UpdateError> {
+            self.inner.update(key, value).await
+        }
+
+        async fn upsert(&self, key: &str, value: &str) -> Result<(), UpsertError> {
+            self.inner.upsert(key, value).await
+        }
+
+        async fn delete(&self, key: &str) -> Result<(), DeleteError> {
+            if key == self.sticky {
+                return Err(DeleteError::MissingKey);
+            }
+            self.inner.delete(key).await
+        }
+
+        async fn delete_if(&self, key: &str, value: &str) -> Result<(), DeleteError> {
+            self.inner.delete_if(key, value).await
+        }
+
+        async fn read(&self, key: &str) -> Result<String, ReadError> {
+            if key == self.hidden {
+                return Err(ReadError::NoData);
+            }
+            self.inner.read(key).await
+        }
+
+        async fn list(&self) -> Result<Vec<String>, ListError> {
+            self.inner.list().await
+        }
+
+        fn bucket(&self, name: &str) -> Storage {
+            self.inner.bucket(name)
+        }
+
+        async fn buckets(&self) -> Result<Vec<String>, ListError> {
+            self.inner.buckets().await
+        }
+    }
+
+    fn requester(id: &str, admin: bool, moderator: bool) -> Option<Requester> {
+        Some(Requester { user_id: id.parse().unwrap(), admin, moderator })
+    }
+
+    #[actix_rt::test]
+    async fn namespaces_do_not_share_notes() {
+        let storage = Storage::memory();
+        let id: Snowflake = "1".parse().unwrap();
+        let guild = Notes::new(storage.clone(), Some(Namespace::Guild(id)), requester("1", false, false));
+        let user = Notes::new(storage.clone(), Some(Namespace::User(id)), requester("1", true, false));
+        guild.save(Scope::Local, "todo", String::from("guild"), None, None).await.unwrap();
+        user.save(Scope::Local, "todo", String::from("user"), None, None).await.unwrap();
+        user.save(Scope::Global, "todo", String::from("global"), None, None).await.unwrap();
+
+        assert_eq!(guild.read(Scope::Local, "todo").await.unwrap().value, "guild");
+        assert_eq!(user.read(Scope::Local, "todo").await.unwrap().value, "user");
+        assert_eq!(Note::decode(storage.read("todo").await.unwrap()).value, "global");
+        let denied = guild.save(Scope::Global, "todo", String::from("mine"), None, None).await;
+        assert!(matches!(denied, Err(InteractionError::GlobalScopeDenied)));
+    }
+
+    #[actix_rt::test]
+    async fn only_author_and_moderators_edit() {
+        let storage = Storage::memory();
+        let guild = Some(Namespace::Guild("1".parse().unwrap()));
+        let author = Notes::new(storage.clone(), guild, requester("2", false, false));
+        let other = Notes::new(storage.clone(), guild, requester("3", false, false));
+        let moderator = Notes::new(storage.clone(), guild, requester("4", false, true));
+        author.save(Scope::Local, "todo", String::from("a"), Some(Visibility::Private), None).await.unwrap();
+
+        let denied = other.save(Scope::Local, "todo", String::from("b"), None, None).await;
+        assert!(matches!(denied, Err(InteractionError::AccessDenied { .. })));
+        assert!(matches!(other.read(Scope::Local, "todo").await, Err(InteractionError::AccessDenied { .. })));
+        assert!(other.list(Scope::Local).await.unwrap().is_empty());
+        assert!(other.authored_by(Scope::Local, "2".parse().unwrap()).await.unwrap().is_empty());
+        assert_eq!(author.authored_by(Scope::Local, "2".parse().unwrap()).await.unwrap().len(), 1);
+
+        let note = moderator.save(Scope::Local, "todo", String::from("c"), None, None).await.unwrap();
+        assert_eq!(note.visibility, Visibility::Private);
+        assert_eq!(note.author_id, Some("2".parse().unwrap()));
+        assert_eq!(author.list(Scope::Local).await.unwrap(), [(String::from("todo"), Visibility::Private)]);
+    }
+
+    #[actix_rt::test]
+    async fn rename_keeps_author() {
+        let storage = Storage::memory();
+        let guild = Some(Namespace::Guild("1".parse().unwrap()));
+        let author = Notes::new(storage.clone(), guild, requester("2", false, false));
+        let other = Notes::new(storage.clone(), guild, requester("3", false, false));
+        author.save(Scope::Local, "a", String::from("text"), None, None).await.unwrap();
+        author.save(Scope::Local, "b", String::from("text"), None, None).await.unwrap();
+
+        let taken = author.rename(Scope::Local, "a", "b").await;
+        assert!(matches!(taken, Err(InteractionError::KeyExists { key: Some(key) }) if key == "b"));
+        assert!(matches!(other.delete(Scope::Local, "a").await, Err(InteractionError::AccessDenied { .. })));
+        author.rename(Scope::Local, "a", "c").await.unwrap();
+        assert_eq!(other.read(Scope::Local, "c").await.unwrap().author_id, Some("2".parse().unwrap()));
+        author.delete(Scope::Local, "c").await.unwrap();
+        assert_eq!(author.list(Scope::Local).await.unwrap(), [(String::from("b"), Visibility::Guild)]);
+    }
+
+    #[test]
+    fn scope_round_trips_through_custom_id() {
+        assert_eq!(Scope::Local.custom_id("set", ""), "set");
+        assert_eq!(Scope::Global.custom_id("ls", "2"), "ls:global:2");
+        assert_eq!(Scope::split_custom_id_args("global:2"), (Scope::Global, "2"));
+        assert_eq!(Scope::split_custom_id_args("global"), (Scope::Global, ""));
+        assert_eq!(Scope::split_custom_id_args("2"), (Scope::Local, "2"));
+        assert_eq!(Scope::split_custom_id_args("globally"), (Scope::Local, "globally"));
+    }
+
+    #[actix_rt::test]
+    async fn failed_rename_leaves_one_note() {
+        let inner = Storage::memory();
+        let storage = Storage::new(FlakyStore { inner: inner.clone(), sticky: "old", hidden: "" });
+        let admin = Notes::new(storage, None, requester("1", true, false));
+        admin.save(Scope::Global, "old", String::from("value"), None, None).await.unwrap();
+
+        assert!(admin.rename(Scope::Global, "old", "new").await.is_err());
+        assert_eq!(inner.list().await.unwrap(), vec![String::from("old")]);
+    }
+
+    #[actix_rt::test]
+    async fn rename_does_not_overwrite_concurrently_saved_note() {
+        let inner = Storage::memory();
+        let storage = Storage::new(FlakyStore { inner: inner.clone(), sticky: "", hidden: "new" });
+        let admin = Notes::new(storage, None, requester("1", true, false));
+        admin.save(Scope::Global, "old", String::from("old value"), None, None).await.unwrap();
+        let concurrent = Note::new(String::from("new value"), "2".parse().unwrap(), Visibility::Guild, Utc::now());
+        inner.upsert("new", &concurrent.encode()).await.unwrap();
+
+        let taken = admin.rename(Scope::Global, "old", "new").await;
+        assert!(matches!(taken, Err(InteractionError::KeyExists { key: Some(key) }) if key == "new"));
+        assert_eq!(Note::decode(inner.read("new").await.unwrap()).value, "new value");
+        assert_eq!(Note::decode(inner.read("old").await.unwrap()).value, "old value");
+    }
+
+    #[actix_rt::test]
+    async fn rename_reuses_key_of_expired_note() {
+        let storage = Storage::memory();
+        let admin = Notes::new(storage.clone(), None, requester("1", true, false));
+        let saved = Utc::now() - Duration::hours(1);
+        let expired = Note::new(String::from("stale"), "1".parse().unwrap(), Visibility::Guild, saved)
+            .with_ttl(Ttl::minutes(30), saved);
+        storage.upsert("new", &expired.encode()).await.unwrap();
+        admin.save(Scope::Global, "old", String::from("value"), None, None).await.unwrap();
+
+        admin.rename(Scope::Global, "old", "new").await.unwrap();
+        assert_eq!(admin.read(Scope::Global, "new").await.unwrap().value, "value");
+    }
+
+    #[actix_rt::test]
+    async fn expired_notes_are_neither_read_nor_listed() {
+        let storage = Storage::memory();
+        let admin = Notes::new(storage.clone(), None, requester("1", true, false));
+        let saved = Utc::now() - Duration::hours(1);
+        let expired = Note::new(String::from("text"), "1".parse().unwrap(), Visibility::Guild, saved)
+            .with_ttl(Ttl::minutes(30), saved);
+        storage.upsert("standup", &expired.encode()).await.unwrap();
+
+        assert!(matches!(admin.read(Scope::Global, "standup").await, Err(InteractionError::KeyNotFound { .. })));
+        assert!(admin.list(Scope::Global).await.unwrap().is_empty());
+        assert!(admin.keys(Scope::Global).await.unwrap().is_empty());
+    }
+}
@@ -0,0 +1,258 @@
+use std::str::FromStr;
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::discord::Snowflake;
+
+/// Who may read a note besides its author
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Visibility {
+    /// Author only
+    Private,
+    /// Everyone who reaches the namespace of the note
+    #[default]
+    Guild,
+    /// Everyone, also global notes to users who are not bot admins
+    Public,
+}
+
+impl Visibility {
+    pub const ALL: [Visibility; 3] = [Visibility::Private, Visibility::Guild, Visibility::Public];
+
+    pub fn code(&self) -> &'static str {
+        match self {
+            Visibility::Private => "private",
+            Visibility::Guild => "guild",
+            Visibility::Public => "public",
+        }
+    }
+}
+
+impl FromStr for Visibility {
+    type Err = ();
+
+    fn from_str(code: &str) -> Result<Self, Self::Err> {
+        Visibility::ALL.into_iter().find(|v| v.code() == code).ok_or(())
+    }
+}
+
+/// Earlier values kept per note, older ones are dropped
+pub const MAX_HISTORY: usize = 10;
+
+/// Value of a note with its metadata, stored as JSON
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Note {
+    pub value: String,
+    /// `None` for notes saved before notes had authors
+    pub author_id: Option<Snowflake>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub visibility: Visibility,
+    /// Number of the current value, counts every change of the value
+    #[serde(default = "first_revision")]
+    pub revision: u32,
+    /// Who wrote the current value
+    #[serde(default)]
+    pub editor_id: Option<Snowflake>,
+    /// Earlier values, oldest first
+    #[serde(default)]
+    pub history: Vec<Revision>,
+    /// Expired notes are treated as missing until the sweeper deletes them
+    #[serde(default)]
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+/// Lifetime given to a note when it is saved
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Ttl {
+    Never,
+    After(Duration),
+}
+
+impl Ttl {
+    /// TTL in minutes as users enter it, 0 never expires
+    pub fn minutes(minutes: u32) -> Self {
+        match minutes {
+            0 => Ttl::Never,
+            minutes => Ttl::After(Duration::minutes(minutes.into())),
+        }
+    }
+
+    pub fn as_minutes(&self) -> u32 {
+        match self {
+            Ttl::Never => 0,
+            Ttl::After(duration) => duration.num_minutes().try_into().unwrap_or(u32::MAX),
+        }
+    }
+
+    fn expiry(&self, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        match self {
+            Ttl::Never => None,
+            Ttl::After(duration) => Some(now + *duration),
+        }
+    }
+}
+
+/// One value a note had
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Revision {
+    pub number: u32,
+    pub value: String,
+    pub editor_id: Option<Snowflake>,
+    pub updated_at: DateTime<Utc>,
+}
+
+fn first_revision() -> u32 {
+    1
+}
+
+impl Note {
+    pub fn new(value: String, author_id: Snowflake, visibility: Visibility, now: DateTime<Utc>) -> Self {
+        Note {
+            value,
+            author_id: Some(author_id),
+            created_at: now,
+            updated_at: now,
+            visibility,
+            revision: first_revision(),
+            editor_id: Some(author_id),
+            history: vec![],
+            expires_at: None,
+        }
+    }
+
+    /// Note which expires `ttl` after `now`
+    pub fn with_ttl(self, ttl: Ttl, now: DateTime<Utc>) -> Self {
+        Note {
+            expires_at: ttl.expiry(now),
+            ..self
+        }
+    }
+
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at.is_some_and(|expires_at| expires_at <= now)
+    }
+
+    /// Note with a new value written by `editor_id`, the current value moves to the history.
+    /// `visibility` is kept when `None`.
+    pub fn updated(
+        mut self,
+        value: String,
+        visibility: Option<Visibility>,
+        editor_id: Snowflake,
+        now: DateTime<Utc>,
+    ) -> Self {
+        let current = self.current();
+        self.history.push(current);
+        if self.history.len() > MAX_HISTORY {
+            self.history.remove(0);
+        }
+        Note {
+            value,
+            updated_at: now,
+            visibility: visibility.unwrap_or(self.visibility),
+            revision: self.revision + 1,
+            editor_id: Some(editor_id),
+            ..self
+        }
+    }
+
+    /// Note with the value of revision `number` as a new revision, `None` if it is not kept
+    pub fn restored(self, number: u32, editor_id: Snowflake, now: DateTime<Utc>) -> Option<Self> {
+        let value = self.revision(number)?.value;
+        Some(self.updated(value, None, editor_id, now))
+    }
+
+    /// Current value as a revision
+    pub fn current(&self) -> Revision {
+        Revision {
+            number: self.revision,
+            value: self.value.clone(),
+            editor_id: self.editor_id,
+            updated_at: self.updated_at,
+        }
+    }
+
+    /// Kept revisions, newest first
+    pub fn revisions(&self) -> impl Iterator<Item = Revision> + '_ {
+        std::iter::once(self.current()).chain(self.history.iter().rev().cloned())
+    }
+
+    pub fn revision(&self, number: u32) -> Option<Revision> {
+        self.revisions().find(|revision| revision.number == number)
+    }
+
+    pub fn encode(&self) -> String {
+        serde_json::to_string(self).expect("note is serializable")
+    }
+
+    /// Note from its stored form. Notes saved before metadata existed are plain text,
+    /// they have no author and stay readable by everyone.
+    pub fn decode(stored: String) -> Self {
+        serde_json::from_str(&stored).unwrap_or_else(|_| {
+            let unknown = DateTime::<Utc>::from(std::time::UNIX_EPOCH);
+            Note {
+                value: stored,
+                author_id: None,
+                created_at: unknown,
+                updated_at: unknown,
+                visibility: Visibility::Public,
+                revision: first_revision(),
+                editor_id: None,
+                history: vec![],
+                expires_at: None,
+            }
+        })
+    }
+
+    pub fn is_author(&self, user_id: Snowflake) -> bool {
+        self.author_id == Some(user_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Note, Ttl, Visibility, MAX_HISTORY};
+    use chrono::{Duration, Utc};
+
+    #[test]
+    fn plain_text_is_a_note_without_author() {
+        let note = Note::new(String::from("text"), "1".parse().unwrap(), Visibility::Private, Utc::now());
+        assert_eq!(Note::decode(note.encode()), note);
+
+        let legacy = Note::decode(String::from("{\"just\": \"json\"}"));
+        assert_eq!(legacy.value, "{\"just\": \"json\"}");
+        assert_eq!(legacy.author_id, None);
+        assert_eq!(legacy.visibility, Visibility::Public);
+    }
+
+    #[test]
+    fn history_is_bounded() {
+        let (author, editor) = ("1".parse().unwrap(), "2".parse().unwrap());
+        let mut note = Note::new(String::from("v1"), author, Visibility::Guild, Utc::now());
+        for n in 2..=15 {
+            note = note.updated(format!("v{}", n), None, editor, Utc::now());
+        }
+        assert_eq!(note.revision, 15);
+        assert_eq!(note.history.len(), MAX_HISTORY);
+        assert_eq!(note.revisions().last().unwrap().number, 5);
+        assert!(note.revision(4).is_none());
+
+        let note = note.restored(5, author, Utc::now()).unwrap();
+        assert_eq!((note.revision, note.value.as_str()), (16, "v5"));
+        assert_eq!(note.editor_id, Some(author));
+        assert_eq!(note.author_id, Some(author));
+    }
+
+    #[test]
+    fn note_expires_after_ttl() {
+        let now = Utc::now();
+        let note = Note::new(String::from("link"), "1".parse().unwrap(), Visibility::Guild, now);
+        let note = note.with_ttl(Ttl::minutes(30), now);
+        assert!(!note.is_expired(now + Duration::minutes(29)));
+        assert!(note.is_expired(now + Duration::minutes(30)));
+        assert!(!note.with_ttl(Ttl::minutes(0), now).is_expired(now + Duration::days(365)));
+    }
+}
@@ -0,0 +1,165 @@
+use crate::discord::interaction::component::{ActionRow, Button};
+use crate::discord::interaction::embed::truncate;
+use crate::domain::component_handlers::custom_id;
+use crate::domain::i18n::{Arg, Translator};
+
+/// Discord limit for message content, also a comfortable size for embed descriptions
+pub const PAGE_LIMIT: usize = 2000;
+
+/// Content split into pages of limited length.
+///
+/// Pages are not stored anywhere: the prev/next buttons carry the target page index
+/// in their `custom_id`, and the component handler rebuilds the pages to render it.
+#[derive(Debug)]
+pub struct Pages {
+    pages: Vec<String>,
+}
+
+impl Pages {
+    /// One item per line. Items never span pages, too long ones are truncated.
+    pub fn from_lines<I, S>(lines: I, limit: usize) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        Self::collect(lines.into_iter().map(|line| truncate(line.as_ref(), limit)), limit)
+    }
+
+    /// Splits at line breaks where possible, otherwise in the middle of the line
+    #[allow(dead_code)]
+    pub fn from_text(text: &str, limit: usize) -> Self {
+        let pieces = text.lines().flat_map(|line| {
+            let chars: Vec<char> = line.chars().collect();
+            if chars.is_empty() {
+                vec![String::new()]
+            } else {
+                chars.chunks(limit).map(|chunk| chunk.iter().collect()).collect()
+            }
+        });
+        Self::collect(pieces, limit)
+    }
+
+    fn collect(pieces: impl Iterator<Item = String>, limit: usize) -> Self {
+        let mut pages = vec![];
+        let mut current = String::new();
+        let mut current_length = 0;
+        for piece in pieces {
+            let length = piece.chars().count();
+            if current_length > 0 && current_length + 1 + length > limit {
+                pages.push(std::mem::take(&mut current));
+                current_length = 0;
+            }
+            if current_length > 0 {
+                current.push('\n');
+                current_length += 1;
+            }
+            current.push_str(&piece);
+            current_length += length;
+        }
+        if current_length > 0 || pages.is_empty() {
+            pages.push(current);
+        }
+        Pages { pages }
+    }
+
+    #[cfg(test)]
+    pub fn len(&self) -> usize {
+        self.pages.len()
+    }
+
+    /// Page at `index`, clamped to the last page as content may shrink between clicks
+    pub fn page(&self, index: usize) -> Page<'_> {
+        let index = index.min(self.pages.len() - 1);
+        Page {
+            index,
+            count: self.pages.len(),
+            content: &self.pages[index],
+        }
+    }
+}
+
+pub struct Page<'p> {
+    pub index: usize,
+    pub count: usize,
+    pub content: &'p str,
+}
+
+impl Page<'_> {
+    /// "Page 2/5"
+    pub fn label(&self, translator: &Translator) -> String {
+        translator.message(
+            "page-label",
+            &[("page", Arg::from(self.index + 1)), ("pages", Arg::from(self.count))],
+        )
+    }
+
+    /// Prev/next buttons routed to the component handler with `custom_id_prefix`.
+    /// Single page needs no buttons.
+    pub fn buttons(&self, custom_id_prefix: &str, translator: &Translator) -> Option<ActionRow> {
+        if self.count <= 1 {
+            return None;
+        }
+        let previous = Button::secondary(
+            &custom_id(custom_id_prefix, &self.index.saturating_sub(1).to_string()),
+            &translator.text("page-previous"),
+        );
+        let next = Button::secondary(
+            &custom_id(custom_id_prefix, &(self.index + 1).to_string()),
+            &translator.text("page-next"),
+        );
+        let previous = if self.index == 0 { previous.disabled() } else { previous };
+        let next = if self.index + 1 == self.count { next.disabled() } else { next };
+        Some(ActionRow::new().with_component(previous).with_component(next))
+    }
+}
+
+/// Page index from `custom_id_args` of a pagination button
+pub fn parse_page(custom_id_args: &str) -> Option<usize> {
+    custom_id_args.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_page, Pages};
+    use crate::domain::i18n::Translator;
+    use serde_json::json;
+
+    #[test]
+    fn split_lines_into_pages() {
+        let pages = Pages::from_lines(["aaaa", "bbbb", "cccc", "dddddddddddd"], 10);
+        assert_eq!(pages.len(), 3);
+        assert_eq!(pages.page(0).content, "aaaa\nbbbb");
+        assert_eq!(pages.page(1).content, "cccc");
+        assert_eq!(pages.page(2).content, "ddddddddd…");
+        assert_eq!(pages.page(7).index, 2);
+    }
+
+    #[test]
+    fn split_long_line_of_text() {
+        let pages = Pages::from_text("short\nxxxxxxxxxxxxxxx", 10);
+        let contents: Vec<&str> = (0..pages.len()).map(|i| pages.page(i).content).collect();
+        assert_eq!(contents, ["short", "xxxxxxxxxx", "xxxxx"]);
+    }
+
+    #[test]
+    fn empty_content_has_one_page() {
+        let pages = Pages::from_lines(Vec::<String>::new(), 10);
+        assert_eq!(pages.len(), 1);
+        assert!(pages.page(0).buttons("ls", &Translator::default()).is_none());
+    }
+
+    #[test]
+    fn buttons_carry_target_page() {
+        let pages = Pages::from_lines(["a", "b", "c"], 1);
+        let buttons = pages.page(0).buttons("ls", &Translator::default()).unwrap();
+        assert_eq!(
+            serde_json::to_value(buttons).unwrap()["components"],
+            json!([
+                {"type": 2, "style": 2, "label": "◀ Previous", "custom_id": "ls:0", "disabled": true},
+                {"type": 2, "style": 2, "label": "Next ▶", "custom_id": "ls:1", "disabled": false}
+            ])
+        );
+        assert_eq!(parse_page("1"), Some(1));
+        assert_eq!(parse_page("next"), None);
+    }
+}
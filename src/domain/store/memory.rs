@@ -0,0 +1,91 @@
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, Mutex};
+
+use async_trait::async_trait;
+
+use super::{DeleteError, InsertError, ListError, NoteStore, ReadError, Storage, UpdateError, UpsertError};
+
+type Buckets = HashMap<Option<String>, BTreeMap<String, String>>;
+
+/// Notes kept in memory, lost on restart
+#[derive(Clone, Default)]
+pub struct MemoryStore {
+    buckets: Arc<Mutex<Buckets>>,
+    name: Option<String>,
+}
+
+impl MemoryStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn with_bucket<T>(&self, f: impl FnOnce(&mut BTreeMap<String, String>) -> T) -> T {
+        let mut buckets = self.buckets.lock().unwrap();
+        f(buckets.entry(self.name.clone()).or_default())
+    }
+}
+
+#[async_trait(?Send)]
+impl NoteStore for MemoryStore {
+    async fn insert(&self, key: &str, value: &str) -> Result<(), InsertError> {
+        self.with_bucket(|notes| {
+            if notes.contains_key(key) {
+                return Err(InsertError::ExistingKey);
+            }
+            notes.insert(key.to_owned(), value.to_owned());
+            Ok(())
+        })
+    }
+
+    async fn update(&self, key: &str, value: &str) -> Result<(), UpdateError> {
+        self.with_bucket(|notes| match notes.get_mut(key) {
+            Some(note) => {
+                *note = value.to_owned();
+                Ok(())
+            }
+            None => Err(UpdateError::MissingKey),
+        })
+    }
+
+    async fn upsert(&self, key: &str, value: &str) -> Result<(), UpsertError> {
+        self.with_bucket(|notes| notes.insert(key.to_owned(), value.to_owned()));
+        Ok(())
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), DeleteError> {
+        self.with_bucket(|notes| notes.remove(key))
+            .map(|_| ())
+            .ok_or(DeleteError::MissingKey)
+    }
+
+    async fn delete_if(&self, key: &str, value: &str) -> Result<(), DeleteError> {
+        self.with_bucket(|notes| match notes.get(key) {
+            Some(current) if current == value => {
+                notes.remove(key);
+                Ok(())
+            }
+            Some(_) => Err(DeleteError::ChangedValue),
+            None => Err(DeleteError::MissingKey),
+        })
+    }
+
+    async fn read(&self, key: &str) -> Result<String, ReadError> {
+        self.with_bucket(|notes| notes.get(key).cloned())
+            .ok_or(ReadError::NoData)
+    }
+
+    async fn list(&self) -> Result<Vec<String>, ListError> {
+        Ok(self.with_bucket(|notes| notes.keys().cloned().collect()))
+    }
+
+    async fn buckets(&self) -> Result<Vec<String>, ListError> {
+        Ok(self.buckets.lock().unwrap().keys().flatten().cloned().collect())
+    }
+
+    fn bucket(&self, name: &str) -> Storage {
+        Storage::new(Self {
+            buckets: self.buckets.clone(),
+            name: Some(name.to_owned()),
+        })
+    }
+}
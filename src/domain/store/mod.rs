@@ -0,0 +1,220 @@
+use std::ops::Deref;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+mod memory;
+mod sled;
+mod sqlite;
+
+pub use self::memory::MemoryStore;
+pub use self::sled::SledStore;
+pub use self::sqlite::SqliteStore;
+
+/// Key-value store of notes. Buckets of a store are independent namespaces sharing its backend.
+#[async_trait(?Send)]
+pub trait NoteStore {
+    /// Fails if the key exists
+    async fn insert(&self, key: &str, value: &str) -> Result<(), InsertError>;
+    /// Fails if the key is missing
+    async fn update(&self, key: &str, value: &str) -> Result<(), UpdateError>;
+    async fn upsert(&self, key: &str, value: &str) -> Result<(), UpsertError>;
+    async fn delete(&self, key: &str) -> Result<(), DeleteError>;
+    /// Deletes the key only while it holds `value`, fails if it was changed in the meantime
+    async fn delete_if(&self, key: &str, value: &str) -> Result<(), DeleteError>;
+    async fn read(&self, key: &str) -> Result<String, ReadError>;
+    /// Keys in ascending order
+    async fn list(&self) -> Result<Vec<String>, ListError>;
+    /// Store in another bucket of the same backend
+    fn bucket(&self, name: &str) -> Storage;
+    /// Names of the named buckets of the backend, in any order. Buckets may be listed while empty.
+    async fn buckets(&self) -> Result<Vec<String>, ListError>;
+}
+
+/// Which backend keeps the notes
+#[derive(Debug, Clone)]
+pub enum StorageConfig {
+    Sled { path: String },
+    Sqlite { path: String },
+    /// Notes are lost on restart, for tests and trying the bot out
+    Memory,
+}
+
+/// Shared handle to a [`NoteStore`]
+#[derive(Clone)]
+pub struct Storage(Arc<dyn NoteStore + Send + Sync>);
+
+impl Storage {
+    pub fn new(store: impl NoteStore + Send + Sync + 'static) -> Self {
+        Storage(Arc::new(store))
+    }
+
+    pub fn open(config: &StorageConfig) -> Result<Self, BackendError> {
+        Ok(match config {
+            StorageConfig::Sled { path } => Storage::new(SledStore::open(path)?),
+            StorageConfig::Sqlite { path } => Storage::new(SqliteStore::open(path)?),
+            StorageConfig::Memory => Storage::memory(),
+        })
+    }
+
+    /// Empty in-memory storage
+    pub fn memory() -> Self {
+        Storage::new(MemoryStore::new())
+    }
+}
+
+impl Deref for Storage {
+    type Target = dyn NoteStore + Send + Sync;
+
+    fn deref(&self) -> &Self::Target {
+        self.0.as_ref()
+    }
+}
+
+/// Failure of the database behind a store
+#[derive(thiserror::Error, Debug)]
+#[error("{}", .0)]
+pub struct BackendError(Box<dyn std::error::Error + Send + Sync>);
+
+impl From<kv::Error> for BackendError {
+    fn from(e: kv::Error) -> Self {
+        BackendError(Box::new(e))
+    }
+}
+
+impl From<rusqlite::Error> for BackendError {
+    fn from(e: rusqlite::Error) -> Self {
+        BackendError(Box::new(e))
+    }
+}
+
+impl From<actix_rt::task::JoinError> for BackendError {
+    fn from(e: actix_rt::task::JoinError) -> Self {
+        BackendError(Box::new(e))
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum ListError {
+    #[error("Error in backend: {}", .0)]
+    Backend(#[from] BackendError),
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum InsertError {
+    #[error("Key exists")]
+    ExistingKey,
+    #[error("Error in backend: {}", .0)]
+    Backend(#[from] BackendError),
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum UpdateError {
+    #[error("Key missing")]
+    MissingKey,
+    #[error("Error in backend: {}", .0)]
+    Backend(#[from] BackendError),
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum UpsertError {
+    #[error("Error in backend: {}", .0)]
+    Backend(#[from] BackendError),
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum DeleteError {
+    #[error("Key missing")]
+    MissingKey,
+    #[error("Value changed")]
+    ChangedValue,
+    #[error("Error in backend: {}", .0)]
+    Backend(#[from] BackendError),
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum ReadError {
+    #[error("Key missing")]
+    #[allow(dead_code)]
+    MissingKey,
+    #[error("No data")]
+    NoData,
+    #[error("Error in backend: {}", .0)]
+    Backend(#[from] BackendError),
+}
+
+/// Backend errors convert into every operation's error, so backends can use `?`
+macro_rules! from_backend {
+    ($($error:ident),*) => {
+        $(
+            impl From<kv::Error> for $error {
+                fn from(e: kv::Error) -> Self {
+                    $error::Backend(e.into())
+                }
+            }
+
+            impl From<rusqlite::Error> for $error {
+                fn from(e: rusqlite::Error) -> Self {
+                    $error::Backend(e.into())
+                }
+            }
+
+            impl From<actix_rt::task::JoinError> for $error {
+                fn from(e: actix_rt::task::JoinError) -> Self {
+                    $error::Backend(e.into())
+                }
+            }
+        )*
+    };
+}
+
+from_backend!(ListError, InsertError, UpdateError, UpsertError, DeleteError, ReadError);
+
+#[cfg(test)]
+mod tests {
+    use super::{DeleteError, InsertError, ReadError, SledStore, SqliteStore, Storage, UpdateError};
+
+    /// Behaviour every backend shares
+    async fn check_store(store: Storage) {
+        store.insert("b", "1").await.unwrap();
+        assert!(matches!(store.insert("b", "2").await, Err(InsertError::ExistingKey)));
+        assert!(matches!(store.update("a", "1").await, Err(UpdateError::MissingKey)));
+        store.upsert("a", "1").await.unwrap();
+        store.update("a", "2").await.unwrap();
+        assert_eq!(store.read("a").await.unwrap(), "2");
+        assert_eq!(store.list().await.unwrap(), ["a", "b"]);
+
+        let other = store.bucket("other");
+        assert!(other.list().await.unwrap().is_empty());
+        other.upsert("a", "3").await.unwrap();
+        assert_eq!(store.read("a").await.unwrap(), "2");
+
+        assert!(matches!(store.delete_if("b", "2").await, Err(DeleteError::ChangedValue)));
+        assert!(matches!(store.delete_if("c", "1").await, Err(DeleteError::MissingKey)));
+        store.delete_if("b", "1").await.unwrap();
+        store.delete("a").await.unwrap();
+        assert!(matches!(store.delete("a").await, Err(DeleteError::MissingKey)));
+        assert!(matches!(store.read("a").await, Err(ReadError::NoData)));
+        assert_eq!(other.read("a").await.unwrap(), "3");
+        assert!(store.list().await.unwrap().is_empty());
+        assert_eq!(store.buckets().await.unwrap(), ["other"]);
+    }
+
+    #[actix_rt::test]
+    async fn memory_store() {
+        check_store(Storage::memory()).await;
+    }
+
+    #[actix_rt::test]
+    async fn sqlite_store() {
+        check_store(Storage::new(SqliteStore::in_memory().unwrap())).await;
+    }
+
+    #[actix_rt::test]
+    async fn sled_store() {
+        let path = std::env::temp_dir().join(format!("disbuster-sled-test-{}", std::process::id()));
+        let store = SledStore::open(path.to_str().unwrap()).unwrap();
+        check_store(Storage::new(store)).await;
+        let _ = std::fs::remove_dir_all(path);
+    }
+}
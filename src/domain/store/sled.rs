@@ -0,0 +1,105 @@
+use async_trait::async_trait;
+use kv::{Bucket, Config, Error, Store};
+
+use super::{BackendError, DeleteError, InsertError, ListError, NoteStore, ReadError, Storage, UpdateError, UpsertError};
+
+/// Tree sled opens for stores created without a bucket name
+const DEFAULT_TREE: &str = "__sled__default";
+
+/// Notes in an embedded sled database, one tree per bucket
+#[derive(Clone)]
+pub struct SledStore {
+    store: Store,
+    name: Option<String>,
+}
+
+impl SledStore {
+    pub fn open(path: &str) -> Result<Self, BackendError> {
+        let config = Config::new(path);
+        let store = Store::new(config)?;
+        Ok(Self { store, name: None })
+    }
+
+    fn get_bucket(&self) -> Result<Bucket<'_, &str, String>, Error> {
+        self.store.bucket::<&str, String>(self.name.as_deref())
+    }
+}
+
+#[async_trait(?Send)]
+impl NoteStore for SledStore {
+    async fn insert(&self, key: &str, value: &str) -> Result<(), InsertError> {
+        let bucket = self.get_bucket()?;
+        if bucket.contains(&key)? {
+            return Err(InsertError::ExistingKey);
+        }
+        bucket.set(&key, &value.to_owned())?;
+        bucket.flush_async().await?;
+        Ok(())
+    }
+
+    async fn update(&self, key: &str, value: &str) -> Result<(), UpdateError> {
+        let bucket = self.get_bucket()?;
+        if !bucket.contains(&key)? {
+            return Err(UpdateError::MissingKey);
+        }
+        bucket.set(&key, &value.to_owned())?;
+        bucket.flush_async().await?;
+        Ok(())
+    }
+
+    async fn upsert(&self, key: &str, value: &str) -> Result<(), UpsertError> {
+        let bucket = self.get_bucket()?;
+        bucket.set(&key, &value.to_owned())?;
+        bucket.flush_async().await?;
+        Ok(())
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), DeleteError> {
+        let bucket = self.get_bucket()?;
+        if !bucket.contains(&key)? {
+            return Err(DeleteError::MissingKey);
+        }
+        bucket.remove(&key)?;
+        bucket.flush_async().await?;
+        Ok(())
+    }
+
+    async fn delete_if(&self, key: &str, value: &str) -> Result<(), DeleteError> {
+        let bucket = self.get_bucket()?;
+        match bucket.compare_and_swap(&key, Some(&value.to_owned()), None) {
+            Ok(()) => {}
+            Err(Error::CompareAndSwap(e)) if e.current.is_some() => return Err(DeleteError::ChangedValue),
+            Err(Error::CompareAndSwap(_)) => return Err(DeleteError::MissingKey),
+            Err(e) => return Err(e.into()),
+        }
+        bucket.flush_async().await?;
+        Ok(())
+    }
+
+    async fn read(&self, key: &str) -> Result<String, ReadError> {
+        let bucket = self.get_bucket()?;
+        bucket.get(&key)?.ok_or(ReadError::NoData)
+    }
+
+    async fn list(&self) -> Result<Vec<String>, ListError> {
+        let bucket = self.get_bucket()?;
+        let mut keys = vec![];
+        for item in bucket.iter() {
+            let item = item?;
+            let key: String = item.key()?;
+            keys.push(key);
+        }
+        Ok(keys)
+    }
+
+    async fn buckets(&self) -> Result<Vec<String>, ListError> {
+        Ok(self.store.buckets().into_iter().filter(|name| name != DEFAULT_TREE).collect())
+    }
+
+    fn bucket(&self, name: &str) -> Storage {
+        Storage::new(Self {
+            store: self.store.clone(),
+            name: Some(name.to_owned()),
+        })
+    }
+}
@@ -0,0 +1,175 @@
+use std::sync::{Arc, Mutex};
+
+use actix_rt::task::spawn_blocking;
+use async_trait::async_trait;
+use rusqlite::{params, Connection, OptionalExtension};
+
+use super::{BackendError, DeleteError, InsertError, ListError, NoteStore, ReadError, Storage, UpdateError, UpsertError};
+
+/// Bucket of stores created without a name
+const DEFAULT_BUCKET: &str = "";
+
+/// Notes in an SQLite database, all buckets share one table
+#[derive(Clone)]
+pub struct SqliteStore {
+    connection: Arc<Mutex<Connection>>,
+    bucket: String,
+}
+
+impl SqliteStore {
+    pub fn open(path: &str) -> Result<Self, BackendError> {
+        Self::with_connection(Connection::open(path)?)
+    }
+
+    /// Database which lives as long as the store
+    #[cfg(test)]
+    pub fn in_memory() -> Result<Self, BackendError> {
+        Self::with_connection(Connection::open_in_memory()?)
+    }
+
+    fn with_connection(connection: Connection) -> Result<Self, BackendError> {
+        connection.execute_batch(
+            "CREATE TABLE IF NOT EXISTS notes (
+                bucket TEXT NOT NULL,
+                key TEXT NOT NULL,
+                value TEXT NOT NULL,
+                PRIMARY KEY (bucket, key)
+            )",
+        )?;
+        Ok(Self {
+            connection: Arc::new(Mutex::new(connection)),
+            bucket: DEFAULT_BUCKET.to_owned(),
+        })
+    }
+
+    /// Runs `query` off the async executor, SQLite calls block
+    async fn run<T, E>(&self, query: impl FnOnce(&Connection, &str) -> Result<T, E> + Send + 'static) -> Result<T, E>
+    where
+        T: Send + 'static,
+        E: From<actix_rt::task::JoinError> + Send + 'static,
+    {
+        let connection = self.connection.clone();
+        let bucket = self.bucket.clone();
+        spawn_blocking(move || query(&connection.lock().unwrap(), &bucket)).await?
+    }
+}
+
+#[async_trait(?Send)]
+impl NoteStore for SqliteStore {
+    async fn insert(&self, key: &str, value: &str) -> Result<(), InsertError> {
+        let (key, value) = (key.to_owned(), value.to_owned());
+        self.run(move |connection, bucket| {
+            let inserted = connection.execute(
+                "INSERT OR IGNORE INTO notes (bucket, key, value) VALUES (?1, ?2, ?3)",
+                params![bucket, key, value],
+            )?;
+            if inserted == 0 {
+                return Err(InsertError::ExistingKey);
+            }
+            Ok(())
+        })
+        .await
+    }
+
+    async fn update(&self, key: &str, value: &str) -> Result<(), UpdateError> {
+        let (key, value) = (key.to_owned(), value.to_owned());
+        self.run(move |connection, bucket| {
+            let updated = connection.execute(
+                "UPDATE notes SET value = ?3 WHERE bucket = ?1 AND key = ?2",
+                params![bucket, key, value],
+            )?;
+            if updated == 0 {
+                return Err(UpdateError::MissingKey);
+            }
+            Ok(())
+        })
+        .await
+    }
+
+    async fn upsert(&self, key: &str, value: &str) -> Result<(), UpsertError> {
+        let (key, value) = (key.to_owned(), value.to_owned());
+        self.run(move |connection, bucket| {
+            connection.execute(
+                "INSERT INTO notes (bucket, key, value) VALUES (?1, ?2, ?3)
+                 ON CONFLICT (bucket, key) DO UPDATE SET value = excluded.value",
+                params![bucket, key, value],
+            )?;
+            Ok(())
+        })
+        .await
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), DeleteError> {
+        let key = key.to_owned();
+        self.run(move |connection, bucket| {
+            let deleted = connection.execute("DELETE FROM notes WHERE bucket = ?1 AND key = ?2", params![bucket, key])?;
+            if deleted == 0 {
+                return Err(DeleteError::MissingKey);
+            }
+            Ok(())
+        })
+        .await
+    }
+
+    async fn delete_if(&self, key: &str, value: &str) -> Result<(), DeleteError> {
+        let (key, value) = (key.to_owned(), value.to_owned());
+        self.run(move |connection, bucket| {
+            let deleted = connection.execute(
+                "DELETE FROM notes WHERE bucket = ?1 AND key = ?2 AND value = ?3",
+                params![bucket, key, value],
+            )?;
+            if deleted == 0 {
+                let exists = connection
+                    .query_row("SELECT 1 FROM notes WHERE bucket = ?1 AND key = ?2", params![bucket, key], |_| Ok(()))
+                    .optional()?;
+                return Err(if exists.is_some() { DeleteError::ChangedValue } else { DeleteError::MissingKey });
+            }
+            Ok(())
+        })
+        .await
+    }
+
+    async fn read(&self, key: &str) -> Result<String, ReadError> {
+        let key = key.to_owned();
+        self.run(move |connection, bucket| {
+            connection
+                .query_row(
+                    "SELECT value FROM notes WHERE bucket = ?1 AND key = ?2",
+                    params![bucket, key],
+                    |row| row.get(0),
+                )
+                .optional()?
+                .ok_or(ReadError::NoData)
+        })
+        .await
+    }
+
+    async fn list(&self) -> Result<Vec<String>, ListError> {
+        self.run(|connection, bucket| {
+            let mut statement = connection.prepare("SELECT key FROM notes WHERE bucket = ?1 ORDER BY key")?;
+            let keys = statement
+                .query_map(params![bucket], |row| row.get(0))?
+                .collect::<Result<Vec<String>, _>>()?;
+            Ok(keys)
+        })
+        .await
+    }
+
+    async fn buckets(&self) -> Result<Vec<String>, ListError> {
+        self.run(|connection, _| {
+            let mut statement = connection.prepare("SELECT DISTINCT bucket FROM notes WHERE bucket != ?1")?;
+            let buckets = statement
+                .query_map(params![DEFAULT_BUCKET], |row| row.get(0))?
+                .collect::<Result<Vec<String>, _>>()?;
+            Ok(buckets)
+        })
+        .await
+    }
+
+    fn bucket(&self, name: &str) -> Storage {
+        Storage::new(Self {
+            connection: self.connection.clone(),
+            bucket: name.to_owned(),
+        })
+    }
+}
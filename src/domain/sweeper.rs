@@ -0,0 +1,84 @@
+use std::time::Duration;
+
+use chrono::Utc;
+use log::{error, info, warn};
+
+use crate::domain::namespace::Namespace;
+use crate::domain::note::Note;
+use crate::domain::store::{ListError, Storage};
+
+/// Deletes expired notes of every namespace, returns how many were removed
+pub async fn sweep_expired(storage: &Storage) -> Result<usize, ListError> {
+    let now = Utc::now();
+    let mut stores = vec![Namespace::Global.bucket(storage)];
+    for name in storage.buckets().await? {
+        // other buckets, like persisted cooldowns, hold no notes
+        if let Some(namespace) = Namespace::of_bucket(&name) {
+            stores.push(namespace.bucket(storage));
+        }
+    }
+    let mut removed = 0;
+    for store in stores {
+        for key in store.list().await? {
+            let stored = match store.read(&key).await {
+                Ok(stored) => stored,
+                Err(e) => {
+                    warn!("Failed to read note {} while sweeping: {}", key, e);
+                    continue;
+                }
+            };
+            // a note saved under the key since reading it is kept
+            if Note::decode(stored.clone()).is_expired(now) && store.delete_if(&key, &stored).await.is_ok() {
+                removed += 1;
+            }
+        }
+    }
+    Ok(removed)
+}
+
+/// Sweeps expired notes every `interval` for as long as the runtime lives
+pub fn spawn_sweeper(storage: Storage, interval: Duration) {
+    actix_rt::spawn(async move {
+        let mut ticks = actix_rt::time::interval(interval);
+        loop {
+            ticks.tick().await;
+            match sweep_expired(&storage).await {
+                Ok(0) => {}
+                Ok(removed) => info!("Removed {} expired notes", removed),
+                Err(e) => error!("Failed to sweep expired notes: {}", e),
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, Utc};
+
+    use super::sweep_expired;
+    use crate::domain::namespace::Namespace;
+    use crate::domain::note::{Note, Ttl, Visibility};
+    use crate::domain::store::Storage;
+
+    #[actix_rt::test]
+    async fn expired_notes_of_every_namespace_are_removed() {
+        let storage = Storage::memory();
+        let guild = Namespace::Guild("1".parse().unwrap()).bucket(&storage);
+        let saved = Utc::now() - Duration::hours(1);
+        let note = |minutes| {
+            Note::new(String::from("text"), "2".parse().unwrap(), Visibility::Guild, saved)
+                .with_ttl(Ttl::minutes(minutes), saved)
+                .encode()
+        };
+        guild.upsert("standup", &note(30)).await.unwrap();
+        guild.upsert("plan", &note(120)).await.unwrap();
+        guild.upsert("rules", &note(0)).await.unwrap();
+        storage.upsert("password", &note(1)).await.unwrap();
+        storage.bucket("cooldowns").upsert("set", "{}").await.unwrap();
+
+        assert_eq!(sweep_expired(&storage).await.unwrap(), 2);
+        assert_eq!(guild.list().await.unwrap(), ["plan", "rules"]);
+        assert!(storage.list().await.unwrap().is_empty());
+        assert_eq!(storage.bucket("cooldowns").list().await.unwrap(), ["set"]);
+    }
+}
@@ -5,7 +5,8 @@ use std::{
 };
 
 use crate::discord::interaction::{Interaction, InteractionCallback};
-use crate::domain::bot::BotContext;
+use crate::discord::multipart::MultipartBody;
+use crate::domain::bot::{BotContext, Get};
 use crate::domain::interaction_pipeline::{InteractionError, InteractionPipeline};
 use actix_rt::task::spawn_blocking;
 use actix_web::{
@@ -19,11 +20,27 @@ pub async fn interactions(
     interaction: Json<Interaction>,
     pipeline: Data<InteractionPipeline<BotContext>>,
     bot_context: Data<BotContext>,
-) -> Result<Json<InteractionCallback>, InteractionError> {
-    pipeline
-        .handle(interaction.into_inner(), &bot_context)
-        .await
-        .map(Json)
+) -> Result<HttpResponse, InteractionError> {
+    let context = bot_context.for_interaction(&interaction);
+    let callback = match pipeline.handle(interaction.into_inner(), &context).await {
+        Ok(callback) => callback,
+        Err(e) => InteractionCallback::channel_message_with_source(e.user_message(context.get())),
+    };
+    callback_response(&callback)
+}
+
+/// Callbacks with files are sent as multipart/form-data, others as JSON
+fn callback_response(callback: &InteractionCallback) -> Result<HttpResponse, InteractionError> {
+    if callback.files().is_empty() {
+        return Ok(HttpResponse::Ok().json(callback));
+    }
+    let multipart = MultipartBody::encode(callback, callback.files()).map_err(|e| {
+        log::error!("Failed to encode callback: {}", e);
+        InteractionError::Unexpected
+    })?;
+    Ok(HttpResponse::Ok()
+        .content_type(multipart.content_type)
+        .body(multipart.body))
 }
 
 #[get("/tos")]
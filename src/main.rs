@@ -1,16 +1,23 @@
+use std::time::Duration;
+
 use actix_web::{middleware, web, App, HttpServer};
 use dotenv::dotenv;
 use endpoints::index;
 
 use crate::configuration::BotConfig;
 use crate::discord_authorization::DiscordAuthorization;
+use crate::domain::declare_commands;
+use crate::domain::i18n::Catalog;
 use crate::domain::store::Storage;
+use crate::domain::sweeper::spawn_sweeper;
+use discord::rest::application_command::ApplicationCommandType;
 use discord::Snowflake;
 
 use crate::endpoints::{interactions, privacy, tos};
 use domain::bot::BotContext;
 use domain::interaction_handlers::*;
-use domain::interaction_pipeline::InteractionPipeline;
+use domain::cooldown::{Cooldown, Cooldowns, RateLimit};
+use domain::interaction_pipeline::{AdminChannelReporter, InteractionPipeline, LoggingMiddleware, Route, RouteError};
 
 mod configuration;
 mod discord;
@@ -25,7 +32,11 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     env_logger::init();
     let config = BotConfig::load_env()?;
     let public_key = config.public_key;
-    let store = Storage::new(config.storage_path.as_str(), None)?;
+    let store = Storage::open(&config.storage)?;
+    let deferral_budget = config.deferral_budget;
+    if let Some(interval) = config.sweep_interval {
+        spawn_sweeper(store.clone(), interval);
+    }
 
     let client = discord::rest::DiscordBotApiClient::new(
         config.token.as_str(),
@@ -34,26 +45,29 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         "0.1",
         config.app_id,
     );
-    // declare_commands(&client).await?;
-    let bot_context = BotContext::new(store, client);
+    let catalog = Catalog::bundled()?;
+    if config.declare_commands {
+        declare_commands(&client, &catalog).await?;
+    }
+    let cooldowns = if config.persist_cooldowns {
+        let cooldowns = cooldowns().with_persistence(store.bucket("cooldowns"));
+        cooldowns.restore().await;
+        cooldowns
+    } else {
+        cooldowns()
+    };
+    let reporter = config
+        .admin_channel_id
+        .map(|channel_id| AdminChannelReporter::new(client.clone(), channel_id));
+    let bot_context = BotContext::new(store, client, catalog).with_admins(config.admin_ids);
+    // Workers build their own pipelines, fail early if routes conflict
+    interaction_pipeline(deferral_budget, cooldowns.clone(), reporter.clone())?;
     HttpServer::new(move || {
+        let pipeline = interaction_pipeline(deferral_budget, cooldowns.clone(), reporter.clone())
+            .expect("routes are checked at startup");
         App::new()
             .app_data(web::Data::new(bot_context.clone()))
-            .app_data(web::Data::new(InteractionPipeline::<BotContext>::new(
-                vec![
-                    Box::new(PingInteractionHandler),
-                    Box::new(InteractionCommandInteractionHandler::from(
-                        EchoCommandHandler,
-                    )),
-                    Box::new(InteractionCommandInteractionHandler::from(
-                        SetCommandHandler,
-                    )),
-                    Box::new(InteractionCommandInteractionHandler::from(LsCommandHandler)),
-                    Box::new(InteractionCommandInteractionHandler::from(
-                        GetCommandHandler,
-                    )),
-                ],
-            )))
+            .app_data(web::Data::new(pipeline))
             .wrap(middleware::Compress::default())
             .service(privacy)
             .service(tos)
@@ -69,3 +83,73 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     .await?;
     Ok(())
 }
+
+/// Writing notes is limited, it hits the storage. Echoes may come in short bursts per channel,
+/// listing the notes of a user reads every note of the guild.
+fn cooldowns() -> Cooldowns {
+    let limit = RateLimit::SlidingWindow {
+        max_uses: 5,
+        window: Duration::from_secs(30),
+    };
+    let echo = RateLimit::TokenBucket {
+        capacity: 3,
+        refill: Duration::from_secs(10),
+    };
+    let notes_by_user = Route::Command {
+        command_type: ApplicationCommandType::User,
+        name: String::from("Notes by user"),
+    };
+    let scan = RateLimit::SlidingWindow {
+        max_uses: 10,
+        window: Duration::from_secs(60),
+    };
+    Cooldowns::new()
+        .with_cooldown(Route::command("echo"), Cooldown::per_channel(echo))
+        .with_cooldown(notes_by_user, Cooldown::per_guild(scan))
+        .with_cooldown(Route::command("set"), Cooldown::per_user(limit))
+        .with_cooldown(Route::ModalSubmit(String::from("set")), Cooldown::per_user(limit))
+        .with_cooldown(Route::command("rename"), Cooldown::per_user(limit))
+        .with_cooldown(Route::command("restore"), Cooldown::per_user(limit))
+        .with_cooldown(Route::Component(String::from("del")), Cooldown::per_user(limit))
+}
+
+fn interaction_pipeline(
+    deferral_budget: Option<Duration>,
+    cooldowns: Cooldowns,
+    reporter: Option<AdminChannelReporter>,
+) -> Result<InteractionPipeline<BotContext>, RouteError> {
+    let pipeline = InteractionPipeline::<BotContext>::new(vec![
+        Box::new(PingInteractionHandler),
+        Box::new(InteractionCommandInteractionHandler::from(
+            EchoCommandHandler,
+        )),
+        Box::new(InteractionCommandInteractionHandler::from(
+            SetCommandHandler,
+        )),
+        Box::new(ModalSubmitInteractionHandler::from(SetModalSubmitHandler)),
+        Box::new(MessageCommandInteractionHandler::from(SaveAsNoteCommandHandler)),
+        Box::new(UserCommandInteractionHandler::from(NotesByUserCommandHandler)),
+        Box::new(InteractionCommandInteractionHandler::from(LsCommandHandler)),
+        Box::new(MessageComponentInteractionHandler::from(LsPageComponentHandler)),
+        Box::new(InteractionCommandInteractionHandler::from(
+            GetCommandHandler,
+        )),
+        Box::new(InteractionCommandInteractionHandler::from(EditCommandHandler)),
+        Box::new(InteractionCommandInteractionHandler::from(RenameCommandHandler)),
+        Box::new(InteractionCommandInteractionHandler::from(DelCommandHandler)),
+        Box::new(MessageComponentInteractionHandler::from(DelConfirmComponentHandler)),
+        Box::new(InteractionCommandInteractionHandler::from(HistoryCommandHandler)),
+        Box::new(InteractionCommandInteractionHandler::from(DiffCommandHandler)),
+        Box::new(InteractionCommandInteractionHandler::from(RestoreCommandHandler)),
+    ])?
+    .with_middleware(LoggingMiddleware)
+    .with_middleware(cooldowns);
+    let pipeline = match reporter {
+        Some(reporter) => pipeline.with_error_reporter(reporter),
+        None => pipeline,
+    };
+    Ok(match deferral_budget {
+        Some(budget) => pipeline.with_deferral_budget(budget),
+        None => pipeline,
+    })
+}